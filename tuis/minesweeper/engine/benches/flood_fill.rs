@@ -0,0 +1,85 @@
+//! Reveal and generation timings on large boards.
+//!
+//! Run with `cargo bench -p minesweeper_engine`.
+
+use std::time::{Duration, Instant};
+
+use minesweeper_engine::{Board, GameState, Grid};
+use rand::{rngs::StdRng, SeedableRng};
+
+const RUNS: u32 = 5;
+
+fn bench(name: &str, mut run: impl FnMut() -> u32) {
+    let mut total = Duration::ZERO;
+    let mut tiles = 0;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        tiles = run();
+        total += start.elapsed();
+    }
+    println!(
+        "{:<40} {:>10.2?} / run, {:>9} tiles",
+        name,
+        total / RUNS,
+        tiles
+    );
+}
+
+/// Reveals `(x, y)` on a fresh game and returns how many tiles opened.
+fn reveal(board: &Board, (x, y): (u16, u16)) -> u32 {
+    let mut game = GameState::new(board.clone());
+    let covered = game.empty_cnt();
+    game.reveal(x, y);
+    covered - game.empty_cnt()
+}
+
+fn main() {
+    let (width, height) = (1000, 1000);
+    let center = (width / 2, height / 2);
+
+    bench("generate 1000x1000, 15% mines", || {
+        let mut rng = StdRng::seed_from_u64(1);
+        let board = Board::generate(Grid::square(width, height), 150_000, &mut rng, Some(center));
+        board.tiles().len() as u32
+    });
+
+    // without mines a single reveal opens all one million tiles
+    let empty = Board::new(Grid::square(width, height));
+    bench("reveal 1000x1000, no mines", || reveal(&empty, center));
+
+    let sparse = Board::generate(
+        Grid::square(width, height),
+        10_000,
+        &mut StdRng::seed_from_u64(2),
+        Some(center),
+    );
+    bench("reveal 1000x1000, 1% mines", || reveal(&sparse, center));
+
+    let dense = Board::generate(
+        Grid::square(width, height),
+        150_000,
+        &mut StdRng::seed_from_u64(3),
+        Some(center),
+    );
+    bench("reveal 1000x1000, 15% mines", || reveal(&dense, center));
+
+    // a long winding corridor, which used to overflow the recursive flood fill:
+    // every fourth row is a wall of mines with a 3 tile gap at alternating ends
+    let (snake_w, snake_h) = (2000, 500);
+    let snake = Board::with_bombs(
+        Grid::square(snake_w, snake_h),
+        (3..snake_h).step_by(4).flat_map(|y| {
+            let gap = if y % 8 == 3 {
+                snake_w - 3..snake_w
+            } else {
+                0..3
+            };
+            (0..snake_w)
+                .filter(move |x| !gap.contains(x))
+                .map(move |x| (x, y))
+        }),
+    );
+    bench("reveal 2000x500 winding corridor", || {
+        reveal(&snake, (0, 0))
+    });
+}
@@ -0,0 +1,273 @@
+use rand::prelude::*;
+
+use crate::{
+    grid::Grid,
+    solver,
+    tile::{Tile, TileContent},
+};
+
+/// How many boards no-guess generation tries before settling for the last one.
+pub const NO_GUESS_ATTEMPTS: u32 = 500;
+
+/// Mine map laid out on a [`Grid`], addressed by `(x, y)`.
+///
+/// Tiles are stored row by row in one flat vector, see [`Grid::index`].
+/// A tile can hold up to [`Board::max_mines`] mines.
+#[derive(Debug, Clone, Default)]
+pub struct Board {
+    grid: Grid,
+    /// total mines, counting every mine of a stacked tile
+    bomb_cnt: u32,
+    max_mines: u8,
+    tiles: Vec<Tile>,
+}
+
+impl Board {
+    /// Creates a covered board without bombs.
+    pub fn new(grid: Grid) -> Self {
+        Self {
+            grid,
+            bomb_cnt: 0,
+            max_mines: 1,
+            tiles: vec![Tile::default(); grid.len()],
+        }
+    }
+
+    /// Creates a covered board with bombs at the given positions.
+    pub fn with_bombs(grid: Grid, bombs: impl IntoIterator<Item = (u16, u16)>) -> Self {
+        Self::with_mines(grid, 1, bombs.into_iter().map(|pos| (pos, 1)))
+    }
+
+    /// Creates a covered board with `(position, mine count)` stacks of mines,
+    /// each clamped to `1..=max_mines`.
+    pub fn with_mines(
+        grid: Grid,
+        max_mines: u8,
+        mines: impl IntoIterator<Item = ((u16, u16), u8)>,
+    ) -> Self {
+        let mut board = Self::new(grid);
+        board.max_mines = max_mines.max(1);
+        for ((x, y), n) in mines {
+            let idx = grid.index(x, y);
+            board.place_mines(idx, n.clamp(1, board.max_mines));
+        }
+        board
+    }
+
+    /// Places `bomb_cnt` bombs at random.
+    ///
+    /// `keep_clear` and its neighbours stay free of bombs if the board has room for it.
+    pub fn generate(
+        grid: Grid,
+        bomb_cnt: u32,
+        rng: &mut impl Rng,
+        keep_clear: Option<(u16, u16)>,
+    ) -> Self {
+        Self::generate_stacked(grid, bomb_cnt, 1, rng, keep_clear)
+    }
+
+    /// Places `mine_cnt` mines at random, up to `max_mines` on one tile.
+    ///
+    /// Like [`Board::generate`], which is the `max_mines == 1` case.
+    pub fn generate_stacked(
+        grid: Grid,
+        mine_cnt: u32,
+        max_mines: u8,
+        rng: &mut impl Rng,
+        keep_clear: Option<(u16, u16)>,
+    ) -> Self {
+        let max_mines = max_mines.max(1);
+        let mut clear = vec![false; grid.len()];
+        if let Some((x, y)) = keep_clear {
+            let area: Vec<usize> = grid
+                .neighbours(x, y)
+                .chain([(x, y)])
+                .map(|(nx, ny)| grid.index(nx, ny))
+                .collect();
+            if mine_cnt as usize <= (grid.len() - area.len()) * max_mines as usize {
+                area.into_iter().for_each(|i| clear[i] = true);
+            }
+        }
+
+        // every tile offers one slot per mine it can hold
+        let mut slots: Vec<usize> = (0..grid.len())
+            .filter(|&i| !clear[i])
+            .flat_map(|i| std::iter::repeat_n(i, max_mines as usize))
+            .collect();
+        slots.shuffle(rng);
+        slots.truncate(mine_cnt as usize);
+
+        let mut counts = vec![0u8; grid.len()];
+        for i in slots {
+            counts[i] += 1;
+        }
+        let mines = counts
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, n)| n > 0)
+            .map(|(i, n)| (grid.position(i), n));
+
+        Self::with_mines(grid, max_mines, mines)
+    }
+
+    /// Generates boards until one can be cleared from `start` without guessing.
+    ///
+    /// Gives up after [`NO_GUESS_ATTEMPTS`] boards and returns the last one,
+    /// which still has an opening at `start`.
+    pub fn generate_no_guess(
+        grid: Grid,
+        bomb_cnt: u32,
+        rng: &mut impl Rng,
+        start: (u16, u16),
+    ) -> Self {
+        let mut board = Self::default();
+        for _ in 0..NO_GUESS_ATTEMPTS {
+            board = Self::generate(grid, bomb_cnt, rng, Some(start));
+            if board.is_solvable_from(start) {
+                break;
+            }
+        }
+        board
+    }
+
+    /// Puts `n` mines on an empty tile, tiles that already hold mines are left alone.
+    fn place_mines(&mut self, idx: usize, n: u8) {
+        if self.tiles[idx].is_bomb() {
+            return;
+        }
+        self.tiles[idx].content = TileContent::Bomb(n);
+        self.bomb_cnt += n as u32;
+
+        let grid = self.grid;
+        for idx in grid.neighbour_indices(idx) {
+            if let TileContent::Empty(num) = self.tiles[idx].content {
+                self.tiles[idx].content = TileContent::Empty(num.saturating_add(n));
+            }
+        }
+    }
+
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    pub fn width(&self) -> u16 {
+        self.grid.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.grid.height
+    }
+
+    /// `(width, height)`
+    pub fn size(&self) -> (u16, u16) {
+        (self.grid.width, self.grid.height)
+    }
+
+    /// Total number of mines.
+    pub fn bomb_cnt(&self) -> u32 {
+        self.bomb_cnt
+    }
+
+    /// Most mines a single tile can hold.
+    pub fn max_mines(&self) -> u8 {
+        self.max_mines.max(1)
+    }
+
+    /// Position of `(x, y)` in the flat tile storage.
+    pub fn index(&self, x: u16, y: u16) -> usize {
+        self.grid.index(x, y)
+    }
+
+    /// Inverse of [`Board::index`].
+    pub fn position(&self, idx: usize) -> (u16, u16) {
+        self.grid.position(idx)
+    }
+
+    pub fn tile(&self, x: u16, y: u16) -> &Tile {
+        &self.tiles[self.index(x, y)]
+    }
+
+    pub fn tile_mut(&mut self, x: u16, y: u16) -> &mut Tile {
+        let idx = self.index(x, y);
+        &mut self.tiles[idx]
+    }
+
+    /// All tiles in row-major order.
+    pub fn tiles(&self) -> &[Tile] {
+        &self.tiles
+    }
+
+    pub fn tiles_mut(&mut self) -> &mut [Tile] {
+        &mut self.tiles
+    }
+
+    pub fn in_bounds(&self, x: i32, y: i32) -> bool {
+        self.grid.in_bounds(x, y)
+    }
+
+    /// The tiles touching `(x, y)`, as laid out by the grid.
+    pub fn neighbours(&self, x: u16, y: u16) -> impl Iterator<Item = (u16, u16)> + '_ {
+        self.grid.neighbours(x, y)
+    }
+
+    /// Checks if the board can be cleared from `start` without ever guessing.
+    ///
+    /// The solver treats every mined tile as a single mine.
+    pub fn is_solvable_from(&self, start: (u16, u16)) -> bool {
+        let mines: Vec<bool> = self.tiles.iter().map(Tile::is_bomb).collect();
+        solver::is_solvable(&self.grid, &mines, self.index(start.0, start.1))
+    }
+
+    /// A tile the board can be cleared from without guessing, if there is one.
+    ///
+    /// Openings (safe tiles without mines around) are tried first, one per area
+    /// of openings since they uncover each other, then the other safe tiles.
+    pub fn solvable_start(&self) -> Option<(u16, u16)> {
+        let (openings, _) = self.openings();
+        let others = (0..self.tiles.len()).filter(|&i| {
+            !self.tiles[i].is_bomb() && self.tiles[i].content != TileContent::Empty(0)
+        });
+        openings
+            .into_iter()
+            .chain(others)
+            .map(|i| self.position(i))
+            .find(|&start| self.is_solvable_from(start))
+    }
+
+    /// The 3BV of the board: the fewest clicks that clear it without flags.
+    ///
+    /// One click per area of openings, one per safe tile no opening uncovers.
+    pub fn three_bv(&self) -> u32 {
+        let (openings, opened) = self.openings();
+        let lone = (0..self.tiles.len())
+            .filter(|&i| !self.tiles[i].is_bomb() && !opened[i])
+            .count();
+        (openings.len() + lone) as u32
+    }
+
+    /// One opening of each area of openings, and the tiles the openings uncover.
+    fn openings(&self) -> (Vec<usize>, Vec<bool>) {
+        let is_opening = |i: usize| self.tiles[i].content == TileContent::Empty(0);
+        let mut opened = vec![false; self.tiles.len()];
+        let mut openings = vec![];
+        for start in 0..self.tiles.len() {
+            if !is_opening(start) || opened[start] {
+                continue;
+            }
+            openings.push(start);
+            opened[start] = true;
+            let mut stack = vec![start];
+            while let Some(i) = stack.pop() {
+                for n in self.grid.neighbour_indices(i) {
+                    if !opened[n] {
+                        opened[n] = true;
+                        if is_opening(n) {
+                            stack.push(n);
+                        }
+                    }
+                }
+            }
+        }
+        (openings, opened)
+    }
+}
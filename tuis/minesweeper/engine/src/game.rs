@@ -0,0 +1,262 @@
+use std::collections::VecDeque;
+
+use crate::{
+    board::Board,
+    tile::{TileContent, TileCover},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Status {
+    #[default]
+    Playing,
+    Won,
+    Lost,
+}
+
+/// What a reveal or chord did to the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The action had no effect (flagged or uncovered tile, finished game, ...).
+    Nothing,
+    /// This many tiles were uncovered.
+    Revealed(u32),
+    /// The last safe tile was uncovered.
+    Won,
+    /// A bomb was uncovered at this position.
+    Lost(u16, u16),
+    /// A bomb was hit at this position and cost a life; the game goes on.
+    Hit(u16, u16),
+}
+
+/// What toggling the flag of a tile did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlagOutcome {
+    /// The tile is uncovered or the game is finished.
+    Nothing,
+    /// The tile now has this cover.
+    Changed(TileCover),
+}
+
+/// A game in progress on a [`Board`].
+#[derive(Debug, Clone, Default)]
+pub struct GameState {
+    board: Board,
+    status: Status,
+    /// covered tiles without a bomb
+    empty_cnt: u32,
+    /// the game is lost when a hit bomb takes the last one
+    lives: u32,
+    /// tiles whose bombs were hit and cost a life
+    hits: Vec<(u16, u16)>,
+}
+
+impl GameState {
+    pub fn new(board: Board) -> Self {
+        let empty_cnt = board
+            .tiles()
+            .iter()
+            .filter(|tile| tile.is_covered() && !tile.is_bomb())
+            .count() as u32;
+        Self {
+            board,
+            status: Status::Playing,
+            empty_cnt,
+            lives: 1,
+            hits: vec![],
+        }
+    }
+
+    /// Gives the player `lives` lives: every hit bomb takes one and the game is
+    /// lost with the last. A new game has one.
+    pub fn with_lives(mut self, lives: u32) -> Self {
+        self.lives = lives;
+        self
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.status != Status::Playing
+    }
+
+    /// Lives left, see [`GameState::with_lives`].
+    pub fn lives(&self) -> u32 {
+        self.lives
+    }
+
+    /// Tiles whose bombs were hit and cost a life, in order.
+    pub fn hits(&self) -> &[(u16, u16)] {
+        &self.hits
+    }
+
+    /// Number of safe tiles still covered.
+    pub fn empty_cnt(&self) -> u32 {
+        self.empty_cnt
+    }
+
+    /// Number of flags placed, counting every flag of a tile.
+    pub fn flag_cnt(&self) -> u32 {
+        self.board
+            .tiles()
+            .iter()
+            .map(|tile| tile.flags() as u32)
+            .sum()
+    }
+
+    /// Uncovers `(x, y)` and, breadth first, every tile reachable through zeros.
+    ///
+    /// Flagged tiles are left alone. Returns how many tiles were uncovered.
+    fn uncover_chaining(&mut self, x: u16, y: u16) -> u32 {
+        let mut cnt = 0;
+        let mut queue = VecDeque::from([(x, y)]);
+        self.board.tile_mut(x, y).cover = None;
+
+        while let Some((x, y)) = queue.pop_front() {
+            cnt += 1;
+
+            if self.board.tile(x, y).content != TileContent::Empty(0) {
+                continue;
+            }
+
+            let grid = *self.board.grid();
+            for (nx, ny) in grid.neighbours(x, y) {
+                let tile = self.board.tile_mut(nx, ny);
+                if tile.is_covered() && !tile.is_flagged() && !tile.is_bomb() {
+                    tile.cover = None;
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+
+        cnt
+    }
+
+    /// A bomb was hit: costs a life, flagging the tile, and loses the game with the last.
+    fn lose(&mut self, x: u16, y: u16) -> Outcome {
+        self.lives = self.lives.saturating_sub(1);
+        if self.lives > 0 {
+            self.hits.push((x, y));
+            let tile = self.board.tile_mut(x, y);
+            if let TileContent::Bomb(n) = tile.content {
+                tile.cover = Some(TileCover::FlagMark(n));
+            }
+            return Outcome::Hit(x, y);
+        }
+
+        self.end();
+        Outcome::Lost(x, y)
+    }
+
+    /// Ends the game as lost, uncovering every bomb; for when time runs out.
+    pub fn end(&mut self) {
+        if self.is_over() {
+            return;
+        }
+        for tile in self.board.tiles_mut().iter_mut() {
+            if tile.is_bomb() {
+                tile.cover = None;
+            }
+        }
+
+        self.status = Status::Lost;
+    }
+
+    /// Uncovers a covered, unflagged tile, opening up the area around zeros.
+    pub fn reveal(&mut self, x: u16, y: u16) -> Outcome {
+        let tile = *self.board.tile(x, y);
+
+        if self.is_over() {
+            return Outcome::Nothing;
+        }
+        if let Some(TileCover::FlagMark(_)) | None = &tile.cover {
+            return Outcome::Nothing;
+        }
+
+        match &tile.content {
+            TileContent::Empty(_) => {
+                let cnt = self.uncover_chaining(x, y);
+
+                self.empty_cnt = self.empty_cnt.saturating_sub(cnt);
+                if self.empty_cnt == 0 {
+                    self.status = Status::Won;
+                    Outcome::Won
+                } else {
+                    Outcome::Revealed(cnt)
+                }
+            }
+            TileContent::Bomb(_) => self.lose(x, y),
+        }
+    }
+
+    /// Cycles the cover of a covered tile: none → flag → question mark → none,
+    /// leaving the question mark out unless `question_marks`.
+    ///
+    /// On boards with stacked mines the flag count goes up to [`Board::max_mines`]
+    /// before the question mark.
+    pub fn toggle_flag(&mut self, x: u16, y: u16, question_marks: bool) -> FlagOutcome {
+        // a hit bomb stays marked
+        if self.is_over() || self.hits.contains(&(x, y)) {
+            return FlagOutcome::Nothing;
+        }
+
+        let max_flags = self.board.max_mines();
+        let tile = self.board.tile_mut(x, y);
+        match &tile.cover {
+            Some(cover) => {
+                let cover = cover.next_cover(max_flags, question_marks);
+                tile.cover = Some(cover);
+                FlagOutcome::Changed(cover)
+            }
+            None => FlagOutcome::Nothing,
+        }
+    }
+
+    /// Reveals every unflagged neighbour of an uncovered number
+    /// once the right amount of flags is placed around it.
+    ///
+    /// Stacked flags count once per flag, like the number counts every mine.
+    pub fn chord(&mut self, x: u16, y: u16) -> Outcome {
+        let tile = *self.board.tile(x, y);
+        let TileContent::Empty(num) = tile.content else {
+            return Outcome::Nothing;
+        };
+        if self.is_over() || tile.is_covered() || num == 0 {
+            return Outcome::Nothing;
+        }
+
+        let neighbours: Vec<(u16, u16)> = self.board.neighbours(x, y).collect();
+        let flags: u32 = neighbours
+            .iter()
+            .map(|&(nx, ny)| self.board.tile(nx, ny).flags() as u32)
+            .sum();
+        if flags != num as u32 {
+            return Outcome::Nothing;
+        }
+
+        // after a hit the other neighbours are still revealed
+        let mut cnt = 0;
+        let mut hit = None;
+        for (nx, ny) in neighbours {
+            match self.reveal(nx, ny) {
+                Outcome::Revealed(n) => cnt += n,
+                Outcome::Nothing => {}
+                outcome @ Outcome::Hit(..) => hit = hit.or(Some(outcome)),
+                outcome => return outcome,
+            }
+        }
+
+        if let Some(hit) = hit {
+            hit
+        } else if cnt == 0 {
+            Outcome::Nothing
+        } else {
+            Outcome::Revealed(cnt)
+        }
+    }
+}
@@ -0,0 +1,391 @@
+pub const DXDY8: [(i32, i32); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+];
+
+/// The tiles a chess knight jumps to.
+const KNIGHT_DXDY: [(i32, i32); 8] = [
+    (-2, -1),
+    (-1, -2),
+    (1, -2),
+    (2, -1),
+    (2, 1),
+    (1, 2),
+    (-1, 2),
+    (-2, 1),
+];
+
+/// The border of the 5x5 square around a tile.
+const RING_DXDY: [(i32, i32); 16] = [
+    (-2, -2),
+    (-1, -2),
+    (0, -2),
+    (1, -2),
+    (2, -2),
+    (2, -1),
+    (2, 0),
+    (2, 1),
+    (2, 2),
+    (1, 2),
+    (0, 2),
+    (-1, 2),
+    (-2, 2),
+    (-2, 1),
+    (-2, 0),
+    (-2, -1),
+];
+
+const ORTHOGONAL_DXDY: [(i32, i32); 4] = [(-1, 0), (0, -1), (1, 0), (0, 1)];
+
+/// Most offsets of a custom neighbourhood: the full 5x5 square around a tile.
+pub const MAX_OFFSETS: usize = 24;
+
+/// Hex neighbours of a tile in an even row. Odd rows are shifted half a tile to the right.
+const HEX_EVEN_DXDY: [(i32, i32); 6] = [(-1, 0), (1, 0), (-1, -1), (0, -1), (-1, 1), (0, 1)];
+const HEX_ODD_DXDY: [(i32, i32); 6] = [(-1, 0), (1, 0), (0, -1), (1, -1), (0, 1), (1, 1)];
+
+/// Shape of the tiles of a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Shape {
+    /// Square tiles with 8 neighbours.
+    #[default]
+    Square,
+    /// Hexagonal tiles with 6 neighbours, odd rows shifted half a tile to the right.
+    Hex,
+}
+
+impl Shape {
+    pub const ALL: [Shape; 2] = [Shape::Square, Shape::Hex];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Shape::Square => "square",
+            Shape::Hex => "hex",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Shape> {
+        Self::ALL.into_iter().find(|shape| shape.name() == name)
+    }
+}
+
+/// Offsets of a user-defined neighbourhood: distinct tiles of the 5x5 square
+/// around a tile, at most [`MAX_OFFSETS`] of them.
+///
+/// Kept small since every [`Grid`] carries one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Offsets {
+    list: [(i8, i8); MAX_OFFSETS],
+    len: u8,
+}
+
+impl Offsets {
+    /// `None` unless there are 1 to [`MAX_OFFSETS`] distinct offsets other than `(0, 0)`,
+    /// each at most 2 tiles away in both directions.
+    pub fn new(offsets: &[(i32, i32)]) -> Option<Self> {
+        let in_square = |&(dx, dy): &(i32, i32)| dx.abs() <= 2 && dy.abs() <= 2;
+        if offsets.is_empty() || offsets.contains(&(0, 0)) || !offsets.iter().all(in_square) {
+            return None;
+        }
+        let unique = offsets
+            .iter()
+            .enumerate()
+            .all(|(i, offset)| !offsets[..i].contains(offset));
+        unique.then(|| Self::from_table(offsets))
+    }
+
+    /// Offsets of a table known to be valid.
+    fn from_table(table: &[(i32, i32)]) -> Self {
+        let mut list = [(0, 0); MAX_OFFSETS];
+        for (entry, &(dx, dy)) in list.iter_mut().zip(table) {
+            *entry = (dx as i8, dy as i8);
+        }
+        Self {
+            list,
+            len: table.len() as u8,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.list[..self.len()]
+            .iter()
+            .map(|&(dx, dy)| (dx as i32, dy as i32))
+    }
+}
+
+/// Which tiles count as the neighbours of a tile on a square grid.
+///
+/// Numbers count the mines of the neighbours, and reveals, chords and the
+/// solver all go through the same neighbours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Neighbourhood {
+    /// The 8 surrounding tiles.
+    #[default]
+    Standard,
+    /// The 8 tiles a chess knight jumps to.
+    Knight,
+    /// The 16 tiles two steps away: the border of the 5x5 square.
+    Ring,
+    /// The 4 tiles sharing an edge.
+    Orthogonal,
+    /// Offsets chosen by the player.
+    Custom(Offsets),
+}
+
+impl Neighbourhood {
+    pub const PRESETS: [Neighbourhood; 4] = [
+        Neighbourhood::Standard,
+        Neighbourhood::Knight,
+        Neighbourhood::Ring,
+        Neighbourhood::Orthogonal,
+    ];
+
+    /// Name of a preset, or the offsets of a custom neighbourhood like `1,2;2,1`.
+    pub fn name(&self) -> String {
+        match self {
+            Neighbourhood::Standard => "standard".to_string(),
+            Neighbourhood::Knight => "knight".to_string(),
+            Neighbourhood::Ring => "ring".to_string(),
+            Neighbourhood::Orthogonal => "orthogonal".to_string(),
+            Neighbourhood::Custom(offsets) => offsets
+                .iter()
+                .map(|(dx, dy)| format!("{},{}", dx, dy))
+                .collect::<Vec<_>>()
+                .join(";"),
+        }
+    }
+
+    /// Inverse of [`Neighbourhood::name`].
+    pub fn from_name(name: &str) -> Option<Neighbourhood> {
+        if let Some(preset) = Self::PRESETS.into_iter().find(|n| n.name() == name) {
+            return Some(preset);
+        }
+        let offsets: Option<Vec<(i32, i32)>> = name
+            .split(';')
+            .map(|pair| {
+                let (dx, dy) = pair.split_once(',')?;
+                Some((dx.trim().parse().ok()?, dy.trim().parse().ok()?))
+            })
+            .collect();
+        Offsets::new(&offsets?).map(Neighbourhood::Custom)
+    }
+
+    fn offsets(&self) -> Offsets {
+        match self {
+            Neighbourhood::Standard => Offsets::from_table(&DXDY8),
+            Neighbourhood::Knight => Offsets::from_table(&KNIGHT_DXDY),
+            Neighbourhood::Ring => Offsets::from_table(&RING_DXDY),
+            Neighbourhood::Orthogonal => Offsets::from_table(&ORTHOGONAL_DXDY),
+            Neighbourhood::Custom(offsets) => *offsets,
+        }
+    }
+}
+
+/// The six directions of a hex grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HexDirection {
+    West,
+    East,
+    NorthWest,
+    NorthEast,
+    SouthWest,
+    SouthEast,
+}
+
+/// Layout of a board: its size and how tiles touch each other.
+///
+/// Tiles are addressed by `(x, y)` or by their row-major index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Grid {
+    pub width: u16,
+    pub height: u16,
+    pub shape: Shape,
+    /// Opposite edges touch each other, making the board a torus.
+    ///
+    /// Hex rows only line up across the top and bottom edge with an even height,
+    /// so hex boards with an odd height wrap left and right only.
+    pub wrap: bool,
+    /// Neighbours of a tile on a square grid; hex grids always use their six.
+    pub neighbourhood: Neighbourhood,
+    /// Layers of a three-dimensional board, stacked from the top down:
+    /// `height` counts the rows of every layer.
+    ///
+    /// With more than one layer a tile touches the 26 tiles of the 3x3x3 cube
+    /// around it, whatever the shape, wrap and neighbourhood.
+    pub layers: u16,
+}
+
+impl Grid {
+    pub fn new(width: u16, height: u16, shape: Shape) -> Self {
+        Self {
+            width,
+            height,
+            shape,
+            wrap: false,
+            neighbourhood: Neighbourhood::Standard,
+            layers: 1,
+        }
+    }
+
+    /// The same grid with its edges wrapping around (or not).
+    pub fn with_wrap(self, wrap: bool) -> Self {
+        Self { wrap, ..self }
+    }
+
+    /// The same grid with another neighbourhood.
+    pub fn with_neighbourhood(self, neighbourhood: Neighbourhood) -> Self {
+        Self {
+            neighbourhood,
+            ..self
+        }
+    }
+
+    /// The same grid split into `layers` layers of `height / layers` rows.
+    pub fn with_layers(self, layers: u16) -> Self {
+        Self {
+            layers: layers.max(1),
+            ..self
+        }
+    }
+
+    /// Rows of one layer.
+    pub fn layer_height(&self) -> u16 {
+        self.height / self.layers.max(1)
+    }
+
+    /// The layer row `y` belongs to.
+    pub fn layer(&self, y: u16) -> u16 {
+        y / self.layer_height().max(1)
+    }
+
+    pub fn is_layered(&self) -> bool {
+        self.layers > 1
+    }
+
+    pub fn square(width: u16, height: u16) -> Self {
+        Self::new(width, height, Shape::Square)
+    }
+
+    /// Number of tiles.
+    pub fn len(&self) -> usize {
+        self.width as usize * self.height as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Row-major position of `(x, y)`.
+    pub fn index(&self, x: u16, y: u16) -> usize {
+        y as usize * self.width as usize + x as usize
+    }
+
+    /// Inverse of [`Grid::index`].
+    pub fn position(&self, idx: usize) -> (u16, u16) {
+        let width = self.width as usize;
+        ((idx % width) as u16, (idx / width) as u16)
+    }
+
+    pub fn in_bounds(&self, x: i32, y: i32) -> bool {
+        0 <= x && x < self.width as i32 && 0 <= y && y < self.height as i32
+    }
+
+    fn offsets(&self, y: u16) -> Offsets {
+        match self.shape {
+            Shape::Square => self.neighbourhood.offsets(),
+            Shape::Hex if y.is_multiple_of(2) => Offsets::from_table(&HEX_EVEN_DXDY),
+            Shape::Hex => Offsets::from_table(&HEX_ODD_DXDY),
+        }
+    }
+
+    /// Most neighbours a tile can have.
+    pub fn max_neighbours(&self) -> usize {
+        if self.is_layered() {
+            26
+        } else {
+            self.offsets(0).len()
+        }
+    }
+
+    /// `(x, y)` moved back onto the grid if the edges wrap, `None` if it is off the grid.
+    fn resolve(&self, x: i32, y: i32) -> Option<(u16, u16)> {
+        let (w, h) = (self.width as i32, self.height as i32);
+        let wrap_y = self.wrap && !(self.shape == Shape::Hex && h % 2 == 1);
+        let x = if self.wrap && w > 0 {
+            x.rem_euclid(w)
+        } else {
+            x
+        };
+        let y = if wrap_y && h > 0 { y.rem_euclid(h) } else { y };
+        self.in_bounds(x, y).then_some((x as u16, y as u16))
+    }
+
+    /// The tiles touching `(x, y)`.
+    ///
+    /// On a wrapping grid narrower than 3 tiles the same tile can be reached through
+    /// several offsets, it is still listed once (and never `(x, y)` itself).
+    pub fn neighbours(&self, x: u16, y: u16) -> impl Iterator<Item = (u16, u16)> + '_ {
+        let offsets = self.offsets(y);
+        let step = move |(dx, dy): (i32, i32)| self.resolve(x as i32 + dx, y as i32 + dy);
+        let layered = self.is_layered();
+        let cnt = if layered { 27 } else { offsets.len() };
+        (0..cnt).filter_map(move |i| {
+            if layered {
+                return self.cube_step(x, y, i);
+            }
+            let mut earlier = offsets.iter().take(i);
+            let pos = step(offsets.iter().nth(i)?)?;
+            let repeated = self.wrap && (pos == (x, y) || earlier.any(|o| step(o) == Some(pos)));
+            (!repeated).then_some(pos)
+        })
+    }
+
+    /// Tile `i` of the 27 in the 3x3x3 cube around `(x, y)` on a layered grid,
+    /// `None` for `(x, y)` itself and outside the grid. Layers never wrap.
+    fn cube_step(&self, x: u16, y: u16, i: usize) -> Option<(u16, u16)> {
+        let i = i as i32;
+        let (dx, dy, dz) = (i % 3 - 1, i / 3 % 3 - 1, i / 9 - 1);
+        let layer_height = self.layer_height() as i32;
+        let (layer, row) = (y as i32 / layer_height, y as i32 % layer_height);
+        let (x, row, layer) = (x as i32 + dx, row + dy, layer + dz);
+        let inside = (0..self.width as i32).contains(&x)
+            && (0..layer_height).contains(&row)
+            && (0..self.layers as i32).contains(&layer);
+        (inside && (dx, dy, dz) != (0, 0, 0))
+            .then_some((x as u16, (layer * layer_height + row) as u16))
+    }
+
+    /// Indices of the tiles touching the tile at `idx`.
+    pub fn neighbour_indices(&self, idx: usize) -> impl Iterator<Item = usize> + '_ {
+        let (x, y) = self.position(idx);
+        self.neighbours(x, y).map(|(nx, ny)| self.index(nx, ny))
+    }
+
+    /// The hex neighbour of `(x, y)` in a direction, if it is on the grid (or the grid wraps).
+    pub fn hex_step(&self, x: u16, y: u16, direction: HexDirection) -> Option<(u16, u16)> {
+        let shift = (y % 2) as i32;
+        let (dx, dy) = match direction {
+            HexDirection::West => (-1, 0),
+            HexDirection::East => (1, 0),
+            HexDirection::NorthWest => (shift - 1, -1),
+            HexDirection::NorthEast => (shift, -1),
+            HexDirection::SouthWest => (shift - 1, 1),
+            HexDirection::SouthEast => (shift, 1),
+        };
+        self.resolve(x as i32 + dx, y as i32 + dy)
+    }
+}
@@ -0,0 +1,24 @@
+//! Minesweeper game rules, independent of any user interface.
+
+/// Mine map.
+pub mod board;
+
+/// Game progress and player actions.
+pub mod game;
+
+/// Board layout and neighbourhoods.
+pub mod grid;
+
+/// Board sizes and mine densities of the usual games.
+pub mod presets;
+
+/// Logical board solver.
+pub mod solver;
+
+/// Single tile of a board.
+pub mod tile;
+
+pub use board::Board;
+pub use game::{FlagOutcome, GameState, Outcome, Status};
+pub use grid::{Grid, HexDirection, Neighbourhood, Offsets, Shape};
+pub use tile::{Tile, TileContent, TileCover};
@@ -0,0 +1,147 @@
+/// Preset board sizes. The classic ones come with their own level, so
+/// they play the classic boards: beginner 9x9 with 10 mines, intermediate
+/// 16x16 with 40 and expert 30x16 with 99.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MapSize {
+    #[default]
+    Large,
+    Normal,
+    Small,
+    Expert,
+    Intermediate,
+    Beginner,
+}
+
+impl MapSize {
+    pub const ALL: [MapSize; 6] = [
+        MapSize::Large,
+        MapSize::Normal,
+        MapSize::Small,
+        MapSize::Expert,
+        MapSize::Intermediate,
+        MapSize::Beginner,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MapSize::Large => "large",
+            MapSize::Normal => "normal",
+            MapSize::Small => "small",
+            MapSize::Expert => "expert",
+            MapSize::Intermediate => "intermediate",
+            MapSize::Beginner => "beginner",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<MapSize> {
+        Self::ALL.into_iter().find(|size| size.name() == name)
+    }
+
+    pub fn up(&self) -> MapSize {
+        match self {
+            MapSize::Large => MapSize::Beginner,
+            MapSize::Normal => MapSize::Large,
+            MapSize::Small => MapSize::Normal,
+            MapSize::Expert => MapSize::Small,
+            MapSize::Intermediate => MapSize::Expert,
+            MapSize::Beginner => MapSize::Intermediate,
+        }
+    }
+
+    pub fn down(&self) -> MapSize {
+        match self {
+            MapSize::Large => MapSize::Normal,
+            MapSize::Normal => MapSize::Small,
+            MapSize::Small => MapSize::Expert,
+            MapSize::Expert => MapSize::Intermediate,
+            MapSize::Intermediate => MapSize::Beginner,
+            MapSize::Beginner => MapSize::Large,
+        }
+    }
+
+    pub fn map_size(&self) -> (u16, u16) {
+        match self {
+            Self::Large => (50, 40),
+            Self::Normal => (40, 30),
+            Self::Small => (20, 15),
+            Self::Expert => (30, 16),
+            Self::Intermediate => (16, 16),
+            Self::Beginner => (9, 9),
+        }
+    }
+
+    /// The level a classic size is played at, whatever level is picked.
+    pub fn level(&self) -> Option<GameLevel> {
+        match self {
+            Self::Expert => Some(GameLevel::Hard),
+            Self::Intermediate => Some(GameLevel::Normal),
+            Self::Beginner => Some(GameLevel::Easy),
+            Self::Large | Self::Normal | Self::Small => None,
+        }
+    }
+}
+
+/// How many mines `tiles` tiles get at `density` (0 to 1), rounded; at least
+/// one, and one tile stays safe.
+pub fn mines_at_density(tiles: u64, density: f64) -> u32 {
+    let mines = (tiles as f64 * density).round() as u64;
+    mines.clamp(1, tiles.saturating_sub(1).max(1)) as u32
+}
+
+/// Game levels are mine densities, those of the classic boards.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GameLevel {
+    #[default]
+    Hard,
+    Normal,
+    Easy,
+}
+
+impl GameLevel {
+    pub const ALL: [GameLevel; 3] = [GameLevel::Easy, GameLevel::Normal, GameLevel::Hard];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameLevel::Hard => "hard",
+            GameLevel::Normal => "normal",
+            GameLevel::Easy => "easy",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<GameLevel> {
+        Self::ALL.into_iter().find(|level| level.name() == name)
+    }
+
+    pub fn up(&self) -> GameLevel {
+        match self {
+            GameLevel::Hard => GameLevel::Easy,
+            GameLevel::Normal => GameLevel::Hard,
+            GameLevel::Easy => GameLevel::Normal,
+        }
+    }
+
+    pub fn down(&self) -> GameLevel {
+        match self {
+            GameLevel::Hard => GameLevel::Normal,
+            GameLevel::Normal => GameLevel::Easy,
+            GameLevel::Easy => GameLevel::Hard,
+        }
+    }
+
+    /// Share of the tiles that hold a mine.
+    pub fn density(&self) -> f64 {
+        match self {
+            // expert: 99 mines on 30x16
+            Self::Hard => 0.206,
+            // intermediate: 40 mines on 16x16
+            Self::Normal => 0.156,
+            // beginner: 10 mines on 9x9
+            Self::Easy => 0.123,
+        }
+    }
+
+    /// Mines of a flat board of `map_size` at this level.
+    pub fn bomb_cnt(&self, (width, height): (u16, u16)) -> u32 {
+        mines_at_density(width as u64 * height as u64, self.density())
+    }
+}
@@ -0,0 +1,193 @@
+use crate::{
+    grid::Grid,
+    tile::{Tile, TileContent},
+};
+
+/// What a player can see of a single tile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Knowledge {
+    Unknown,
+    Mine,
+    Revealed(u8),
+}
+
+impl Knowledge {
+    /// What a player sees of `tile`: flags are taken for mines.
+    pub fn of(tile: &Tile) -> Self {
+        match tile.content {
+            _ if tile.is_flagged() => Self::Mine,
+            TileContent::Empty(n) if !tile.is_covered() => Self::Revealed(n),
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Tiles the solver could prove safe or mined from the current view.
+#[derive(Debug, Default)]
+pub struct Deduction {
+    pub safe: Vec<usize>,
+    pub mines: Vec<usize>,
+}
+
+impl Deduction {
+    pub fn is_empty(&self) -> bool {
+        self.safe.is_empty() && self.mines.is_empty()
+    }
+}
+
+/// Constraint of a revealed number: `mines` of the `unknown` tiles are mines.
+struct Constraint {
+    unknown: Vec<usize>,
+    mines: usize,
+}
+
+fn constraints(grid: &Grid, view: &[Knowledge]) -> Vec<Constraint> {
+    let mut result = vec![];
+    for (idx, knowledge) in view.iter().enumerate() {
+        let Knowledge::Revealed(num) = knowledge else {
+            continue;
+        };
+
+        let mut unknown = vec![];
+        let mut known_mines = 0;
+        for n in grid.neighbour_indices(idx) {
+            match view[n] {
+                Knowledge::Unknown => unknown.push(n),
+                Knowledge::Mine => known_mines += 1,
+                Knowledge::Revealed(_) => {}
+            }
+        }
+
+        if !unknown.is_empty() {
+            result.push(Constraint {
+                unknown,
+                mines: (*num as usize).saturating_sub(known_mines),
+            });
+        }
+    }
+    result
+}
+
+/// Finds every tile that can be proven safe or mined using single numbers
+/// and pairs of numbers whose unknown tiles overlap as a subset.
+pub fn deduce(grid: &Grid, view: &[Knowledge]) -> Deduction {
+    let constraints = constraints(grid, view);
+    let mut safe = vec![false; view.len()];
+    let mut mines = vec![false; view.len()];
+
+    for c in &constraints {
+        if c.mines == 0 {
+            c.unknown.iter().for_each(|&i| safe[i] = true);
+        } else if c.mines == c.unknown.len() {
+            c.unknown.iter().for_each(|&i| mines[i] = true);
+        }
+    }
+
+    // a constraint can only be a subset of the ones sharing its first unknown tile
+    let mut by_tile = vec![vec![]; view.len()];
+    for (ci, c) in constraints.iter().enumerate() {
+        c.unknown.iter().for_each(|&i| by_tile[i].push(ci));
+    }
+
+    for a in &constraints {
+        for b in by_tile[a.unknown[0]].iter().map(|&ci| &constraints[ci]) {
+            if a.unknown.len() >= b.unknown.len()
+                || !a.unknown.iter().all(|i| b.unknown.contains(i))
+                || b.mines < a.mines
+            {
+                continue;
+            }
+
+            let rest: Vec<usize> = b
+                .unknown
+                .iter()
+                .copied()
+                .filter(|i| !a.unknown.contains(i))
+                .collect();
+            let rest_mines = b.mines - a.mines;
+            if rest_mines == 0 {
+                rest.iter().for_each(|&i| safe[i] = true);
+            } else if rest_mines == rest.len() {
+                rest.iter().for_each(|&i| mines[i] = true);
+            }
+        }
+    }
+
+    Deduction {
+        safe: (0..view.len()).filter(|&i| safe[i]).collect(),
+        mines: (0..view.len()).filter(|&i| mines[i]).collect(),
+    }
+}
+
+/// The unknown tile least likely to be a mine, for when nothing can be deduced.
+///
+/// A tile next to numbers is as risky as the worst of them makes it; the tiles
+/// away from numbers share the mines the numbers leave over. `mine_cnt` is the
+/// number of mines on the whole board.
+pub fn guess(grid: &Grid, view: &[Knowledge], mine_cnt: u32) -> Option<usize> {
+    let mut risk: Vec<Option<f64>> = vec![None; view.len()];
+    for c in constraints(grid, view) {
+        let p = c.mines as f64 / c.unknown.len() as f64;
+        for i in c.unknown {
+            risk[i] = Some(risk[i].map_or(p, |r| r.max(p)));
+        }
+    }
+
+    let unknown: Vec<usize> = (0..view.len())
+        .filter(|&i| view[i] == Knowledge::Unknown)
+        .collect();
+    let known_mines = view.iter().filter(|&&k| k == Knowledge::Mine).count();
+    let frontier_mines: f64 = risk.iter().flatten().sum();
+    let interior = unknown.iter().filter(|&&i| risk[i].is_none()).count();
+    let interior_risk =
+        (mine_cnt as f64 - known_mines as f64 - frontier_mines).max(0.0) / interior.max(1) as f64;
+
+    let risk_of = |i: usize| risk[i].unwrap_or(interior_risk);
+    unknown
+        .into_iter()
+        .min_by(|&a, &b| risk_of(a).total_cmp(&risk_of(b)))
+}
+
+/// Reveals `idx` on the view, opening neighbours of zeros like the game does.
+fn reveal(grid: &Grid, numbers: &[u8], view: &mut [Knowledge], idx: usize) {
+    let mut stack = vec![idx];
+    while let Some(i) = stack.pop() {
+        if view[i] != Knowledge::Unknown {
+            continue;
+        }
+        view[i] = Knowledge::Revealed(numbers[i]);
+        if numbers[i] == 0 {
+            stack.extend(grid.neighbour_indices(i));
+        }
+    }
+}
+
+/// Checks if a board can be cleared from `start` without ever guessing.
+pub fn is_solvable(grid: &Grid, mines: &[bool], start: usize) -> bool {
+    if mines[start] {
+        return false;
+    }
+
+    let numbers: Vec<u8> = (0..mines.len())
+        .map(|i| grid.neighbour_indices(i).filter(|&n| mines[n]).count() as u8)
+        .collect();
+    let mut view = vec![Knowledge::Unknown; mines.len()];
+    reveal(grid, &numbers, &mut view, start);
+
+    loop {
+        let deduction = deduce(grid, &view);
+        if deduction.is_empty() {
+            break;
+        }
+        for i in deduction.mines {
+            view[i] = Knowledge::Mine;
+        }
+        for i in deduction.safe {
+            reveal(grid, &numbers, &mut view, i);
+        }
+    }
+
+    view.iter()
+        .zip(mines)
+        .all(|(k, &mine)| mine || matches!(k, Knowledge::Revealed(_)))
+}
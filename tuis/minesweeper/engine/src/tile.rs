@@ -0,0 +1,75 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileContent {
+    /// Number of mines on the neighbouring tiles.
+    Empty(u8),
+    /// Number of mines on this tile, at least one.
+    Bomb(u8),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileCover {
+    Empty,
+    QuestionMark,
+    /// Number of flags placed, one per suspected mine.
+    FlagMark(u8),
+}
+
+impl TileCover {
+    /// The cover after the next flag toggle: none → 1 flag → ... → `max_flags` flags
+    /// → question mark → none. Without `question_marks` the last flag goes
+    /// straight back to none.
+    pub fn next_cover(&self, max_flags: u8, question_marks: bool) -> Self {
+        match *self {
+            Self::Empty => Self::FlagMark(1),
+            Self::FlagMark(n) if n < max_flags => Self::FlagMark(n + 1),
+            Self::FlagMark(_) if question_marks => Self::QuestionMark,
+            Self::FlagMark(_) => Self::Empty,
+            Self::QuestionMark => Self::Empty,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tile {
+    pub content: TileContent,
+    pub cover: Option<TileCover>,
+}
+
+impl Default for Tile {
+    fn default() -> Self {
+        Self {
+            content: TileContent::Empty(0),
+            cover: Some(TileCover::Empty),
+        }
+    }
+}
+
+impl Tile {
+    pub fn is_bomb(&self) -> bool {
+        matches!(self.content, TileContent::Bomb(_))
+    }
+
+    /// Number of mines on this tile.
+    pub fn mines(&self) -> u8 {
+        match self.content {
+            TileContent::Bomb(n) => n,
+            TileContent::Empty(_) => 0,
+        }
+    }
+
+    pub fn is_covered(&self) -> bool {
+        self.cover.is_some()
+    }
+
+    pub fn is_flagged(&self) -> bool {
+        matches!(self.cover, Some(TileCover::FlagMark(_)))
+    }
+
+    /// Number of flags on this tile.
+    pub fn flags(&self) -> u8 {
+        match self.cover {
+            Some(TileCover::FlagMark(n)) => n,
+            _ => 0,
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use minesweeper_engine::{
+    solver::{self, Knowledge},
+    Board, Grid, TileContent,
+};
+use proptest::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Counts the bombs around `(x, y)` the slow way.
+fn bombs_around(board: &Board, x: u16, y: u16) -> u8 {
+    let mut cnt = 0;
+    for dy in -1..=1 {
+        for dx in -1..=1 {
+            let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+            if (dx, dy) != (0, 0)
+                && board.in_bounds(nx, ny)
+                && board.tile(nx as u16, ny as u16).is_bomb()
+            {
+                cnt += 1;
+            }
+        }
+    }
+    cnt
+}
+
+/// `(width, height, bomb_cnt)` with at least one safe tile.
+fn board_params() -> impl Strategy<Value = (u16, u16, u32)> {
+    (2u16..40, 2u16..40).prop_flat_map(|(w, h)| (Just(w), Just(h), 0..(w as u32 * h as u32)))
+}
+
+proptest! {
+    #[test]
+    fn generate_places_exact_bomb_cnt((w, h, bombs) in board_params(), seed: u64) {
+        let board = Board::generate(Grid::square(w, h), bombs, &mut StdRng::seed_from_u64(seed), None);
+
+        prop_assert_eq!(board.bomb_cnt(), bombs);
+        prop_assert_eq!(board.tiles().iter().filter(|t| t.is_bomb()).count() as u32, bombs);
+    }
+
+    #[test]
+    fn generate_numbers_count_neighbour_bombs((w, h, bombs) in board_params(), seed: u64) {
+        let board = Board::generate(Grid::square(w, h), bombs, &mut StdRng::seed_from_u64(seed), None);
+
+        for y in 0..h {
+            for x in 0..w {
+                if let TileContent::Empty(num) = board.tile(x, y).content {
+                    prop_assert_eq!(num, bombs_around(&board, x, y), "at ({}, {})", x, y);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn generate_starts_fully_covered((w, h, bombs) in board_params(), seed: u64) {
+        let board = Board::generate(Grid::square(w, h), bombs, &mut StdRng::seed_from_u64(seed), None);
+
+        prop_assert!(board.tiles().iter().all(|t| t.is_covered()));
+    }
+
+    #[test]
+    fn generate_keeps_start_clear(
+        (w, h, bombs) in board_params(),
+        seed: u64,
+        (sx, sy) in (0u16..40, 0u16..40),
+    ) {
+        let start = (sx % w, sy % h);
+        prop_assume!(bombs + 9 <= w as u32 * h as u32);
+        let board = Board::generate(Grid::square(w, h), bombs, &mut StdRng::seed_from_u64(seed), Some(start));
+
+        prop_assert_eq!(board.tile(start.0, start.1).content, TileContent::Empty(0));
+        prop_assert_eq!(board.bomb_cnt(), bombs);
+    }
+
+    #[test]
+    fn generate_is_deterministic_per_seed((w, h, bombs) in board_params(), seed: u64) {
+        let a = Board::generate(Grid::square(w, h), bombs, &mut StdRng::seed_from_u64(seed), None);
+        let b = Board::generate(Grid::square(w, h), bombs, &mut StdRng::seed_from_u64(seed), None);
+
+        prop_assert_eq!(a.tiles(), b.tiles());
+    }
+
+    #[test]
+    fn position_inverts_index(w in 1u16..100, h in 1u16..100, x in 0u16..100, y in 0u16..100) {
+        let board = Board::new(Grid::square(w, h));
+        let (x, y) = (x % w, y % h);
+
+        prop_assert_eq!(board.position(board.index(x, y)), (x, y));
+    }
+}
+
+proptest! {
+    #[test]
+    fn stacked_generation_counts_every_mine(
+        (w, h, mines) in board_params(),
+        max_mines in 1u8..5,
+        seed: u64,
+    ) {
+        let mines = mines * max_mines as u32;
+        let board = Board::generate_stacked(
+            Grid::square(w, h),
+            mines,
+            max_mines,
+            &mut StdRng::seed_from_u64(seed),
+            None,
+        );
+
+        prop_assert_eq!(board.bomb_cnt(), mines);
+        prop_assert_eq!(board.tiles().iter().map(|t| t.mines() as u32).sum::<u32>(), mines);
+        prop_assert!(board.tiles().iter().all(|t| t.mines() <= max_mines));
+        for y in 0..h {
+            for x in 0..w {
+                if let TileContent::Empty(num) = board.tile(x, y).content {
+                    let cnt: u8 = board.neighbours(x, y).map(|(nx, ny)| board.tile(nx, ny).mines()).sum();
+                    prop_assert_eq!(num, cnt);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn single_stack_generation_matches_classic() {
+    let grid = Grid::square(12, 9);
+    let classic = Board::generate(grid, 20, &mut StdRng::seed_from_u64(4), Some((3, 3)));
+    let stacked = Board::generate_stacked(grid, 20, 1, &mut StdRng::seed_from_u64(4), Some((3, 3)));
+
+    assert_eq!(classic.tiles(), stacked.tiles());
+}
+
+#[test]
+fn with_mines_stacks_numbers() {
+    let board = Board::with_mines(Grid::square(3, 3), 3, [((0, 0), 3), ((2, 2), 1)]);
+
+    assert_eq!(board.bomb_cnt(), 4);
+    assert_eq!(board.max_mines(), 3);
+    assert_eq!(board.tile(0, 0).content, TileContent::Bomb(3));
+    assert_eq!(board.tile(1, 1).content, TileContent::Empty(4));
+    assert_eq!(board.tile(1, 0).content, TileContent::Empty(3));
+}
+
+#[test]
+fn with_bombs_counts_numbers() {
+    let board = Board::with_bombs(Grid::square(3, 3), [(0, 0), (2, 2)]);
+
+    assert_eq!(board.bomb_cnt(), 2);
+    assert_eq!(board.tile(1, 1).content, TileContent::Empty(2));
+    assert_eq!(board.tile(2, 0).content, TileContent::Empty(0));
+    assert_eq!(board.tile(1, 0).content, TileContent::Empty(1));
+}
+
+#[test]
+fn with_bombs_ignores_duplicates() {
+    let board = Board::with_bombs(Grid::square(3, 3), [(1, 1), (1, 1)]);
+
+    assert_eq!(board.bomb_cnt(), 1);
+    assert_eq!(board.tile(0, 0).content, TileContent::Empty(1));
+}
+
+#[test]
+fn no_guess_board_is_solvable() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let board = Board::generate_no_guess(Grid::square(16, 16), 40, &mut rng, (7, 7));
+
+    assert_eq!(board.tile(7, 7).content, TileContent::Empty(0));
+    assert!(board.is_solvable_from((7, 7)));
+}
+
+#[test]
+fn solvable_start_prefers_openings() {
+    //   0 1 2
+    // 0 1 1 1
+    // 1 1 * 1
+    // 2 1 1 1
+    // 3 . . .
+    // a number alone leaves the mine among several tiles, the opening does not
+    let board = Board::with_bombs(Grid::square(3, 4), [(1, 1)]);
+    assert_eq!(board.solvable_start(), Some((0, 3)));
+
+    // a 50/50: whatever tile is uncovered first, the other two stay a guess
+    //   0 1
+    // 0 * 2
+    // 1 2 *
+    // 2 1 1
+    let board = Board::with_bombs(Grid::square(2, 3), [(0, 0), (1, 1)]);
+    assert_eq!(board.solvable_start(), None);
+}
+
+#[test]
+fn three_bv_counts_openings_and_lone_numbers() {
+    //   0 1 2 3 4
+    // 0 . 1 * 2 *
+    // 1 . 1 1 2 1
+    // 2 . . . . .
+    let board = Board::with_bombs(Grid::square(5, 3), [(2, 0), (4, 0)]);
+    // the opening clears everything but (3, 0)
+    assert_eq!(board.three_bv(), 2);
+
+    //   0 1 2
+    // 0 1 * 1
+    let board = Board::with_bombs(Grid::square(3, 1), [(1, 0)]);
+    assert_eq!(board.three_bv(), 2);
+    assert_eq!(Board::new(Grid::square(4, 4)).three_bv(), 1);
+}
+
+#[test]
+fn guesses_take_the_least_risky_tile() {
+    use Knowledge::{Mine, Revealed, Unknown};
+    // the 1 puts a mine on one of its two sides
+    let grid = Grid::square(7, 1);
+    let view = [
+        Unknown,
+        Revealed(1),
+        Unknown,
+        Unknown,
+        Unknown,
+        Unknown,
+        Unknown,
+    ];
+
+    // with one mine in all, the 1 accounts for it and the far tiles are safe
+    assert_eq!(solver::guess(&grid, &view, 1), Some(3));
+    // with four, 3 mines share the 4 far tiles: a side of the 1 is better
+    assert_eq!(solver::guess(&grid, &view, 4), Some(0));
+    // nothing left to guess
+    let grid = Grid::square(2, 1);
+    assert_eq!(solver::guess(&grid, &[Revealed(1), Mine], 1), None);
+}
@@ -0,0 +1,200 @@
+use minesweeper_engine::{Board, FlagOutcome, GameState, Grid, Outcome, Status, TileCover};
+
+//   0 1 2 3 4
+// 0 . . . 1 *
+// 1 . . . 1 1
+// 2 1 1 . . .
+// 3 * 1 . . .
+fn game() -> GameState {
+    GameState::new(Board::with_bombs(Grid::square(5, 4), [(4, 0), (0, 3)]))
+}
+
+#[test]
+fn reveal_opens_area_around_zeros_diagonally() {
+    let mut game = game();
+
+    assert_eq!(game.reveal(2, 1), Outcome::Won);
+    assert_eq!(game.status(), Status::Won);
+    assert_eq!(game.empty_cnt(), 0);
+}
+
+#[test]
+fn reveal_number_opens_single_tile() {
+    let mut game = game();
+
+    assert_eq!(game.reveal(3, 0), Outcome::Revealed(1));
+    assert_eq!(game.empty_cnt(), 17);
+    assert!(game.board().tile(2, 0).is_covered());
+}
+
+#[test]
+fn reveal_skips_flagged_tiles() {
+    let mut game = game();
+    game.toggle_flag(3, 1, true);
+
+    assert_eq!(game.reveal(3, 1), Outcome::Nothing);
+    assert_eq!(game.reveal(0, 0), Outcome::Revealed(17));
+    assert!(game.board().tile(3, 1).is_flagged());
+}
+
+#[test]
+fn reveal_bomb_loses_and_shows_bombs() {
+    let mut game = game();
+
+    assert_eq!(game.reveal(4, 0), Outcome::Lost(4, 0));
+    assert_eq!(game.status(), Status::Lost);
+    assert!(!game.board().tile(0, 3).is_covered());
+    assert_eq!(game.reveal(0, 0), Outcome::Nothing);
+}
+
+#[test]
+fn lives_turn_a_bomb_into_a_hit() {
+    let mut game = game().with_lives(2);
+
+    assert_eq!(game.reveal(4, 0), Outcome::Hit(4, 0));
+    assert_eq!(game.status(), Status::Playing);
+    assert_eq!(game.lives(), 1);
+    assert_eq!(game.hits(), [(4, 0)]);
+    // the hit bomb stays flagged
+    assert!(game.board().tile(4, 0).is_flagged());
+    assert_eq!(game.toggle_flag(4, 0, true), FlagOutcome::Nothing);
+    assert!(game.board().tile(0, 3).is_covered());
+
+    assert_eq!(game.reveal(0, 3), Outcome::Lost(0, 3));
+    assert_eq!(game.status(), Status::Lost);
+}
+
+#[test]
+fn chord_goes_on_after_a_hit() {
+    let mut game = game().with_lives(2);
+    game.reveal(1, 2);
+    game.toggle_flag(1, 3, true);
+
+    assert_eq!(game.chord(1, 2), Outcome::Hit(0, 3));
+    assert!(!game.board().tile(2, 3).is_covered());
+
+    // only the wrong flag is left
+    game.toggle_flag(1, 3, true);
+    game.toggle_flag(1, 3, true);
+    assert_eq!(game.reveal(1, 3), Outcome::Won);
+}
+
+#[test]
+fn end_loses_the_game() {
+    let mut game = game();
+    game.end();
+
+    assert_eq!(game.status(), Status::Lost);
+    assert!(!game.board().tile(4, 0).is_covered());
+    assert_eq!(game.reveal(2, 1), Outcome::Nothing);
+}
+
+#[test]
+fn toggle_flag_cycles_covers() {
+    let mut game = game();
+
+    assert_eq!(
+        game.toggle_flag(0, 0, true),
+        FlagOutcome::Changed(TileCover::FlagMark(1))
+    );
+    assert_eq!(game.flag_cnt(), 1);
+    assert_eq!(
+        game.toggle_flag(0, 0, true),
+        FlagOutcome::Changed(TileCover::QuestionMark)
+    );
+    assert_eq!(
+        game.toggle_flag(0, 0, true),
+        FlagOutcome::Changed(TileCover::Empty)
+    );
+
+    game.reveal(3, 0);
+    assert_eq!(game.toggle_flag(3, 0, true), FlagOutcome::Nothing);
+}
+
+#[test]
+fn toggle_flag_can_skip_question_marks() {
+    let mut game = game();
+
+    assert_eq!(
+        game.toggle_flag(0, 0, false),
+        FlagOutcome::Changed(TileCover::FlagMark(1))
+    );
+    assert_eq!(
+        game.toggle_flag(0, 0, false),
+        FlagOutcome::Changed(TileCover::Empty)
+    );
+}
+
+#[test]
+fn chord_needs_matching_flags() {
+    let mut game = game();
+    game.reveal(3, 1);
+
+    assert_eq!(game.chord(3, 1), Outcome::Nothing);
+
+    game.toggle_flag(4, 0, true);
+    assert_eq!(game.chord(3, 1), Outcome::Won);
+}
+
+#[test]
+fn chord_with_wrong_flag_loses() {
+    let mut game = game();
+    game.reveal(1, 2);
+    game.toggle_flag(1, 3, true);
+
+    assert_eq!(game.chord(1, 2), Outcome::Lost(0, 3));
+}
+
+//    0  1  2
+// 0 *3  4  .
+// 1 *1  4  .
+// 2  1  1  .
+fn stacked_game() -> GameState {
+    GameState::new(Board::with_mines(
+        Grid::square(3, 3),
+        3,
+        [((0, 0), 3), ((0, 1), 1)],
+    ))
+}
+
+#[test]
+fn stacked_flags_count_up_to_max_mines() {
+    let mut game = stacked_game();
+
+    let covers: Vec<FlagOutcome> = (0..5).map(|_| game.toggle_flag(0, 0, true)).collect();
+    assert_eq!(
+        covers,
+        [
+            FlagOutcome::Changed(TileCover::FlagMark(1)),
+            FlagOutcome::Changed(TileCover::FlagMark(2)),
+            FlagOutcome::Changed(TileCover::FlagMark(3)),
+            FlagOutcome::Changed(TileCover::QuestionMark),
+            FlagOutcome::Changed(TileCover::Empty),
+        ]
+    );
+}
+
+#[test]
+fn stacked_chord_counts_every_flag() {
+    let mut game = stacked_game();
+    assert_eq!(game.reveal(1, 0), Outcome::Revealed(1));
+
+    game.toggle_flag(0, 0, true);
+    game.toggle_flag(0, 1, true);
+    assert_eq!(game.flag_cnt(), 2);
+    assert_eq!(game.chord(1, 0), Outcome::Nothing);
+
+    game.toggle_flag(0, 0, true);
+    game.toggle_flag(0, 0, true);
+    assert_eq!(game.flag_cnt(), 4);
+    assert_eq!(game.chord(1, 0), Outcome::Revealed(5));
+    assert_eq!(game.reveal(0, 2), Outcome::Won);
+}
+
+#[test]
+fn stacked_mine_loses_like_a_single_one() {
+    let mut game = stacked_game();
+
+    assert_eq!(game.reveal(0, 0), Outcome::Lost(0, 0));
+    assert_eq!(game.status(), Status::Lost);
+}
@@ -0,0 +1,264 @@
+use minesweeper_engine::{
+    solver, Board, GameState, Grid, HexDirection, Neighbourhood, Outcome, Shape, TileContent,
+};
+use proptest::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+fn sorted(mut positions: Vec<(u16, u16)>) -> Vec<(u16, u16)> {
+    positions.sort();
+    positions
+}
+
+#[test]
+fn square_tiles_have_eight_neighbours() {
+    let grid = Grid::square(5, 5);
+
+    assert_eq!(grid.max_neighbours(), 8);
+    assert_eq!(grid.neighbours(2, 2).count(), 8);
+    assert_eq!(
+        sorted(grid.neighbours(0, 0).collect()),
+        [(0, 1), (1, 0), (1, 1)]
+    );
+}
+
+#[test]
+fn neighbourhood_presets() {
+    let grid = |n| Grid::square(7, 7).with_neighbourhood(n);
+
+    assert_eq!(grid(Neighbourhood::Knight).max_neighbours(), 8);
+    assert_eq!(
+        sorted(grid(Neighbourhood::Knight).neighbours(0, 0).collect()),
+        [(1, 2), (2, 1)]
+    );
+    assert_eq!(grid(Neighbourhood::Ring).neighbours(3, 3).count(), 16);
+    assert!(grid(Neighbourhood::Ring)
+        .neighbours(3, 3)
+        .all(|(x, y)| x.abs_diff(3) == 2 || y.abs_diff(3) == 2));
+    assert_eq!(
+        sorted(grid(Neighbourhood::Orthogonal).neighbours(3, 3).collect()),
+        [(2, 3), (3, 2), (3, 4), (4, 3)]
+    );
+}
+
+#[test]
+fn neighbourhood_names() {
+    for preset in Neighbourhood::PRESETS {
+        assert_eq!(Neighbourhood::from_name(&preset.name()), Some(preset));
+    }
+
+    let custom = Neighbourhood::from_name("0,-1; 2,0").unwrap();
+    assert_eq!(custom.name(), "0,-1;2,0");
+    assert_eq!(
+        sorted(
+            Grid::square(5, 5)
+                .with_neighbourhood(custom)
+                .neighbours(2, 2)
+                .collect()
+        ),
+        [(2, 1), (4, 2)]
+    );
+
+    // no offsets, the tile itself, repeated offsets, too far away, not numbers
+    for name in ["", "0,0", "1,0;1,0", "3,0", "a,b", "1"] {
+        assert_eq!(Neighbourhood::from_name(name), None, "{}", name);
+    }
+}
+
+#[test]
+fn knight_boards_play_by_knight_moves() {
+    //   0 1 2
+    // 0 * . .
+    // 1 . . 1
+    // 2 . 1 .
+    let grid = Grid::square(3, 3).with_neighbourhood(Neighbourhood::Knight);
+    let board = Board::with_bombs(grid, [(0, 0)]);
+    assert_eq!(board.tile(2, 1).content, TileContent::Empty(1));
+    assert_eq!(board.tile(1, 1).content, TileContent::Empty(0));
+
+    // (1, 1) has no knight moves on a 3x3 board, so it opens nothing else
+    let mut game = GameState::new(board.clone());
+    assert_eq!(game.reveal(1, 1), Outcome::Revealed(1));
+    // the other tiles form a knight's tour around the mine: the zeros open it all
+    assert_eq!(game.reveal(0, 2), Outcome::Won);
+
+    let mines: Vec<bool> = board.tiles().iter().map(|t| t.is_bomb()).collect();
+    assert!(!solver::is_solvable(&grid, &mines, grid.index(1, 1)));
+}
+
+#[test]
+fn hex_rows_are_offset() {
+    let grid = Grid::new(5, 5, Shape::Hex);
+
+    assert_eq!(grid.max_neighbours(), 6);
+    // even rows lean left
+    assert_eq!(
+        sorted(grid.neighbours(2, 2).collect()),
+        [(1, 1), (1, 2), (1, 3), (2, 1), (2, 3), (3, 2)]
+    );
+    // odd rows lean right
+    assert_eq!(
+        sorted(grid.neighbours(2, 1).collect()),
+        [(1, 1), (2, 0), (2, 2), (3, 0), (3, 1), (3, 2)]
+    );
+    assert_eq!(sorted(grid.neighbours(0, 0).collect()), [(0, 1), (1, 0)]);
+}
+
+#[test]
+fn hex_neighbours_are_symmetric() {
+    let grid = Grid::new(7, 6, Shape::Hex);
+
+    for idx in 0..grid.len() {
+        for other in grid.neighbour_indices(idx) {
+            assert!(grid.neighbour_indices(other).any(|i| i == idx));
+        }
+    }
+}
+
+#[test]
+fn hex_steps_match_neighbours() {
+    let grid = Grid::new(5, 5, Shape::Hex);
+    let directions = [
+        HexDirection::West,
+        HexDirection::East,
+        HexDirection::NorthWest,
+        HexDirection::NorthEast,
+        HexDirection::SouthWest,
+        HexDirection::SouthEast,
+    ];
+
+    for (x, y) in [(2, 2), (2, 1)] {
+        let steps: Vec<(u16, u16)> = directions
+            .iter()
+            .filter_map(|&d| grid.hex_step(x, y, d))
+            .collect();
+        assert_eq!(sorted(steps), sorted(grid.neighbours(x, y).collect()));
+    }
+    assert_eq!(grid.hex_step(2, 2, HexDirection::NorthEast), Some((2, 1)));
+    assert_eq!(grid.hex_step(2, 1, HexDirection::NorthEast), Some((3, 0)));
+    assert_eq!(grid.hex_step(0, 2, HexDirection::NorthWest), None);
+}
+
+#[test]
+fn hex_flood_fill_follows_hex_neighbours() {
+    // on a square grid (1, 1) would touch the bomb and stop the cascade
+    let grid = Grid::new(4, 3, Shape::Hex);
+    let board = Board::with_bombs(grid, [(0, 0)]);
+    assert_eq!(board.tile(1, 1).content, TileContent::Empty(0));
+
+    let mut game = GameState::new(board);
+    assert_eq!(game.reveal(3, 2), Outcome::Won);
+}
+
+#[test]
+fn torus_tiles_all_have_full_neighbourhoods() {
+    let grid = Grid::square(5, 4).with_wrap(true);
+
+    for idx in 0..grid.len() {
+        assert_eq!(grid.neighbour_indices(idx).count(), 8);
+    }
+    assert_eq!(
+        sorted(grid.neighbours(0, 0).collect()),
+        [
+            (0, 1),
+            (0, 3),
+            (1, 0),
+            (1, 1),
+            (1, 3),
+            (4, 0),
+            (4, 1),
+            (4, 3)
+        ]
+    );
+
+    let hex = Grid::new(6, 4, Shape::Hex).with_wrap(true);
+    for idx in 0..hex.len() {
+        assert_eq!(hex.neighbour_indices(idx).count(), 6);
+    }
+    assert_eq!(hex.hex_step(0, 0, HexDirection::NorthWest), Some((5, 3)));
+}
+
+#[test]
+fn narrow_torus_lists_each_neighbour_once() {
+    let grid = Grid::square(2, 3).with_wrap(true);
+
+    // (-1, y) and (1, y) are the same column
+    assert_eq!(
+        sorted(grid.neighbours(0, 0).collect()),
+        [(0, 1), (0, 2), (1, 0), (1, 1), (1, 2)]
+    );
+}
+
+#[test]
+fn torus_numbers_and_cascades_wrap() {
+    let grid = Grid::square(5, 5).with_wrap(true);
+    let board = Board::with_bombs(grid, [(2, 2)]);
+    assert_eq!(board.tile(0, 0).content, TileContent::Empty(0));
+    assert_eq!(board.tile(1, 1).content, TileContent::Empty(1));
+
+    let flat = Board::with_bombs(Grid::square(5, 5), [(0, 0)]);
+    let torus = Board::with_bombs(grid, [(0, 0)]);
+    assert_eq!(flat.tile(4, 4).content, TileContent::Empty(0));
+    assert_eq!(torus.tile(4, 4).content, TileContent::Empty(1));
+
+    // the opening at (0, 0) reaches the other side of the board through the edges
+    let mut game = GameState::new(Board::with_bombs(grid, [(2, 2)]));
+    assert_eq!(game.reveal(0, 0), Outcome::Won);
+}
+
+#[test]
+fn layered_tiles_touch_the_cube_around_them() {
+    // three 3x3 layers, rows 0-2, 3-5 and 6-8
+    let grid = Grid::square(3, 9).with_layers(3);
+    assert_eq!((grid.layer_height(), grid.layer(4)), (3, 1));
+    assert_eq!(grid.max_neighbours(), 26);
+
+    // the centre of the middle layer
+    assert_eq!(grid.neighbours(1, 4).count(), 26);
+    // a corner of the top layer: its own layer and the same corner one layer down
+    assert_eq!(
+        sorted(grid.neighbours(0, 2).collect()),
+        [(0, 1), (0, 4), (0, 5), (1, 1), (1, 2), (1, 4), (1, 5)]
+    );
+}
+
+#[test]
+fn layered_numbers_and_cascades_cross_layers() {
+    let grid = Grid::square(3, 9).with_layers(3);
+    let board = Board::with_bombs(grid, [(1, 1)]);
+    // the tile below the mine in the middle layer and a corner of it
+    assert_eq!(board.tile(1, 4).content, TileContent::Empty(1));
+    assert_eq!(board.tile(0, 3).content, TileContent::Empty(1));
+    // the bottom layer is too far away
+    assert_eq!(board.tile(1, 7).content, TileContent::Empty(0));
+
+    // the opening of the bottom layer stops at the numbers of the middle one
+    let mut game = GameState::new(board);
+    assert_eq!(game.reveal(1, 7), Outcome::Revealed(18));
+    assert!(game.board().tile(0, 0).is_covered());
+}
+
+proptest! {
+    #[test]
+    fn numbers_count_neighbour_bombs(
+        w in 2u16..30,
+        h in 2u16..30,
+        hex: bool,
+        wrap: bool,
+        density in 0.0..0.5f64,
+        seed: u64,
+    ) {
+        let shape = if hex { Shape::Hex } else { Shape::Square };
+        let grid = Grid::new(w, h, shape).with_wrap(wrap);
+        let bombs = (grid.len() as f64 * density) as u32;
+        let board = Board::generate(grid, bombs, &mut StdRng::seed_from_u64(seed), None);
+
+        for y in 0..h {
+            for x in 0..w {
+                if let TileContent::Empty(num) = board.tile(x, y).content {
+                    let cnt = grid.neighbours(x, y).filter(|&(nx, ny)| board.tile(nx, ny).is_bomb()).count();
+                    prop_assert_eq!(num as usize, cnt);
+                }
+            }
+        }
+    }
+}
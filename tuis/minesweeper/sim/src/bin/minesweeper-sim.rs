@@ -0,0 +1,19 @@
+use anyhow::{anyhow, Result};
+use minesweeper_sim::{Format, Options};
+
+fn main() -> Result<()> {
+    let options = Options::parse(std::env::args().skip(1))
+        .map_err(|e| anyhow!("{}\nTry `minesweeper-sim --help` for more information.", e))?;
+
+    if options.help {
+        print!("{}", minesweeper_sim::USAGE);
+        return Ok(());
+    }
+
+    let summaries = minesweeper_sim::run(&options);
+    match options.format {
+        Format::Json => print!("{}", minesweeper_sim::to_json(&summaries)),
+        Format::Csv => print!("{}", minesweeper_sim::to_csv(&summaries)),
+    }
+    Ok(())
+}
@@ -0,0 +1,429 @@
+//! Headless games played by strategies, for benchmarks.
+
+use std::{
+    fmt::Write as _,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, bail, Result};
+use minesweeper_engine::{
+    presets::{GameLevel, MapSize},
+    solver::{self, Deduction, Knowledge},
+    Board, GameState, Grid, Status,
+};
+use rand::{prelude::*, rngs::StdRng};
+
+pub const USAGE: &str = "\
+Usage: minesweeper-sim [OPTIONS]
+
+Plays generated boards without a terminal and prints how a strategy did on
+every difficulty: win rate, 3BV of the boards and timings.
+
+Options:
+  -n, --boards <N>          boards per difficulty (default 100)
+      --strategy <LIST>     strategies to play with, comma separated: random,
+                            solver (random guesses when stuck), probability
+                            (guesses the tile least likely to be a mine)
+                            (default: all)
+      --difficulty <LIST>   difficulties to play, comma separated, like
+                            small-easy, large-hard or expert (default: every
+                            size and level preset and the classic boards)
+      --format <FORMAT>     json (default) or csv
+      --seed <N>            seed of the first board, the next ones count up
+                            (default 0)
+      --no-guess            generate boards that can be cleared without guessing
+  -h, --help                print this help and exit
+
+The first click is in the middle of the board and never hits a mine: the
+boards keep it and its neighbours clear, like the safe first click setting.
+";
+
+/// How the simulated player picks the next tile to uncover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// any covered tile
+    Random,
+    /// the tiles the solver proves safe, a random covered tile when it is stuck
+    Solver,
+    /// like `Solver`, but guessing the tile least likely to be a mine
+    Probability,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 3] = [Strategy::Random, Strategy::Solver, Strategy::Probability];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Strategy::Random => "random",
+            Strategy::Solver => "solver",
+            Strategy::Probability => "probability",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Strategy> {
+        Self::ALL
+            .into_iter()
+            .find(|strategy| strategy.name() == name)
+    }
+}
+
+/// How the results are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Csv,
+}
+
+/// A board size and mine count to play.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Difficulty {
+    pub name: String,
+    pub map_size: (u16, u16),
+    pub bomb_cnt: u32,
+}
+
+impl Difficulty {
+    /// Every size and level preset of the menu, like `small-easy`, and the
+    /// classic boards, like `expert`.
+    pub fn presets() -> Vec<Difficulty> {
+        MapSize::ALL
+            .into_iter()
+            .flat_map(|size| {
+                let levels = match size.level() {
+                    Some(level) => vec![(size.name().to_string(), level)],
+                    None => GameLevel::ALL
+                        .into_iter()
+                        .map(|level| (format!("{}-{}", size.name(), level.name()), level))
+                        .collect(),
+                };
+                levels.into_iter().map(move |(name, level)| Difficulty {
+                    name,
+                    map_size: size.map_size(),
+                    bomb_cnt: level.bomb_cnt(size.map_size()),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Simulation options.
+#[derive(Debug)]
+pub struct Options {
+    pub boards: u32,
+    pub strategies: Vec<Strategy>,
+    pub difficulties: Vec<Difficulty>,
+    pub format: Format,
+    pub seed: u64,
+    pub no_guess: bool,
+    pub help: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            boards: 100,
+            strategies: Strategy::ALL.to_vec(),
+            difficulties: Difficulty::presets(),
+            format: Format::Json,
+            seed: 0,
+            no_guess: false,
+            help: false,
+        }
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T> {
+    value
+        .parse()
+        .map_err(|_| anyhow!("invalid number `{}` for {}", value, flag))
+}
+
+impl Options {
+    /// Parses the arguments, without the program name.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            // accept both `--flag value` and `--flag=value`
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(value.to_string()))
+                }
+                _ => (arg, None),
+            };
+            let mut value = || {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| anyhow!("{} needs a value", flag))
+            };
+
+            match flag.as_str() {
+                "-n" | "--boards" => options.boards = parse_number(&flag, &value()?)?,
+                "--strategy" => {
+                    options.strategies = value()?
+                        .split(',')
+                        .map(|name| {
+                            Strategy::from_name(name).ok_or_else(|| {
+                                anyhow!(
+                                    "unknown strategy `{}` (expected random, solver or probability)",
+                                    name
+                                )
+                            })
+                        })
+                        .collect::<Result<_>>()?;
+                }
+                "--difficulty" => {
+                    let presets = Difficulty::presets();
+                    options.difficulties = value()?
+                        .split(',')
+                        .map(|name| {
+                            presets
+                                .iter()
+                                .find(|difficulty| difficulty.name == name)
+                                .cloned()
+                                .ok_or_else(|| {
+                                    anyhow!(
+                                        "unknown difficulty `{}` (expected a size and a level \
+                                         like small-easy, or beginner, intermediate or expert)",
+                                        name
+                                    )
+                                })
+                        })
+                        .collect::<Result<_>>()?;
+                }
+                "--format" => {
+                    options.format = match value()?.as_str() {
+                        "json" => Format::Json,
+                        "csv" => Format::Csv,
+                        other => bail!("unknown format `{}` (expected json or csv)", other),
+                    }
+                }
+                "--seed" => options.seed = parse_number(&flag, &value()?)?,
+                "--no-guess" => options.no_guess = true,
+                "-h" | "--help" => options.help = true,
+                _ => bail!("unknown option `{}`", flag),
+            }
+        }
+
+        if options.boards == 0 {
+            bail!("--boards must be at least 1");
+        }
+        Ok(options)
+    }
+}
+
+/// How one strategy did on the boards of one difficulty.
+#[derive(Debug, Clone)]
+pub struct Summary {
+    pub difficulty: Difficulty,
+    pub strategy: Strategy,
+    pub wins: u32,
+    /// 3BV of every board, sorted
+    pub three_bv: Vec<u32>,
+    /// time spent generating the boards
+    pub generate_time: Duration,
+    /// time spent playing them
+    pub play_time: Duration,
+}
+
+impl Summary {
+    pub fn boards(&self) -> u32 {
+        self.three_bv.len() as u32
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        self.wins as f64 / self.boards().max(1) as f64
+    }
+
+    /// The 3BV `p` of the way through the boards, 0.5 being the median.
+    pub fn three_bv_at(&self, p: f64) -> u32 {
+        let last = self.three_bv.len().saturating_sub(1);
+        self.three_bv
+            .get((last as f64 * p).round() as usize)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    pub fn three_bv_mean(&self) -> f64 {
+        self.three_bv.iter().sum::<u32>() as f64 / self.boards().max(1) as f64
+    }
+
+    /// Milliseconds per board.
+    fn per_board_ms(&self, time: Duration) -> f64 {
+        time.as_secs_f64() * 1000.0 / self.boards().max(1) as f64
+    }
+}
+
+/// Where the first click goes, like a new game puts the cursor.
+fn start_of((width, height): (u16, u16)) -> (u16, u16) {
+    (width.max(2) / 2 - 1, height.max(2) / 2 - 1)
+}
+
+/// Plays `board` from `start` until it is won or lost; whether it was won.
+pub fn play(board: Board, start: (u16, u16), strategy: Strategy, rng: &mut impl Rng) -> bool {
+    let grid = *board.grid();
+    let mine_cnt = board.bomb_cnt();
+    let mut game = GameState::new(board);
+    game.reveal(start.0, start.1);
+
+    while !game.is_over() {
+        let view: Vec<Knowledge> = game.board().tiles().iter().map(Knowledge::of).collect();
+        let deduction = match strategy {
+            Strategy::Random => Deduction::default(),
+            Strategy::Solver | Strategy::Probability => solver::deduce(&grid, &view),
+        };
+        if !deduction.is_empty() {
+            // flagged mines count as known on the next look
+            for idx in deduction.mines {
+                let (x, y) = grid.position(idx);
+                game.toggle_flag(x, y, false);
+            }
+            for idx in deduction.safe {
+                let (x, y) = grid.position(idx);
+                game.reveal(x, y);
+            }
+            continue;
+        }
+
+        let guess = match strategy {
+            Strategy::Probability => solver::guess(&grid, &view, mine_cnt),
+            Strategy::Random | Strategy::Solver => (0..view.len())
+                .filter(|&i| view[i] == Knowledge::Unknown)
+                .choose(rng),
+        };
+        let Some(idx) = guess else {
+            break;
+        };
+        let (x, y) = grid.position(idx);
+        game.reveal(x, y);
+    }
+    game.status() == Status::Won
+}
+
+/// Generates the boards of every difficulty and plays each with every strategy.
+///
+/// Board `i` of a difficulty is generated from `seed + i` and every strategy
+/// plays the same boards.
+pub fn run(options: &Options) -> Vec<Summary> {
+    let mut summaries = vec![];
+    for difficulty in &options.difficulties {
+        let (width, height) = difficulty.map_size;
+        let grid = Grid::square(width, height);
+        let start = start_of(difficulty.map_size);
+        let mut results: Vec<Summary> = options
+            .strategies
+            .iter()
+            .map(|&strategy| Summary {
+                difficulty: difficulty.clone(),
+                strategy,
+                wins: 0,
+                three_bv: vec![],
+                generate_time: Duration::ZERO,
+                play_time: Duration::ZERO,
+            })
+            .collect();
+
+        for i in 0..options.boards {
+            let seed = options.seed.wrapping_add(i as u64);
+            let mut rng = StdRng::seed_from_u64(seed);
+            let generating = Instant::now();
+            let board = if options.no_guess {
+                Board::generate_no_guess(grid, difficulty.bomb_cnt, &mut rng, start)
+            } else {
+                Board::generate(grid, difficulty.bomb_cnt, &mut rng, Some(start))
+            };
+            let generate_time = generating.elapsed();
+            let three_bv = board.three_bv();
+
+            for summary in results.iter_mut() {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let playing = Instant::now();
+                let won = play(board.clone(), start, summary.strategy, &mut rng);
+                summary.play_time += playing.elapsed();
+                summary.generate_time += generate_time;
+                summary.wins += won as u32;
+                summary.three_bv.push(three_bv);
+            }
+        }
+
+        for summary in results.iter_mut() {
+            summary.three_bv.sort_unstable();
+        }
+        summaries.extend(results);
+    }
+    summaries
+}
+
+/// Columns of [`to_csv`] and keys of [`to_json`]; times are per board.
+const COLUMNS: [&str; 16] = [
+    "difficulty",
+    "width",
+    "height",
+    "mines",
+    "strategy",
+    "boards",
+    "wins",
+    "win_rate",
+    "bbbv_min",
+    "bbbv_p25",
+    "bbbv_median",
+    "bbbv_p75",
+    "bbbv_max",
+    "bbbv_mean",
+    "generate_ms",
+    "play_ms",
+];
+
+/// The values of a summary, in the order of [`COLUMNS`].
+fn values(summary: &Summary) -> [String; 16] {
+    let (width, height) = summary.difficulty.map_size;
+    [
+        summary.difficulty.name.clone(),
+        width.to_string(),
+        height.to_string(),
+        summary.difficulty.bomb_cnt.to_string(),
+        summary.strategy.name().to_string(),
+        summary.boards().to_string(),
+        summary.wins.to_string(),
+        format!("{:.4}", summary.win_rate()),
+        summary.three_bv_at(0.0).to_string(),
+        summary.three_bv_at(0.25).to_string(),
+        summary.three_bv_at(0.5).to_string(),
+        summary.three_bv_at(0.75).to_string(),
+        summary.three_bv_at(1.0).to_string(),
+        format!("{:.2}", summary.three_bv_mean()),
+        format!("{:.3}", summary.per_board_ms(summary.generate_time)),
+        format!("{:.3}", summary.per_board_ms(summary.play_time)),
+    ]
+}
+
+/// A header line, then one line per summary.
+pub fn to_csv(summaries: &[Summary]) -> String {
+    let mut text = String::new();
+    let _ = writeln!(text, "{}", COLUMNS.join(","));
+    for summary in summaries {
+        let _ = writeln!(text, "{}", values(summary).join(","));
+    }
+    text
+}
+
+/// An array with one object per summary.
+pub fn to_json(summaries: &[Summary]) -> String {
+    let mut text = String::from("[\n");
+    for (i, summary) in summaries.iter().enumerate() {
+        let members: Vec<String> = COLUMNS
+            .iter()
+            .zip(values(summary))
+            .map(|(&key, value)| match key {
+                "difficulty" | "strategy" => format!("\"{}\": \"{}\"", key, value),
+                _ => format!("\"{}\": {}", key, value),
+            })
+            .collect();
+        let comma = if i + 1 < summaries.len() { "," } else { "" };
+        let _ = writeln!(text, "  {{{}}}{}", members.join(", "), comma);
+    }
+    text.push_str("]\n");
+    text
+}
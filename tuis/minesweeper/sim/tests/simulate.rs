@@ -0,0 +1,98 @@
+use minesweeper_engine::{Board, Grid};
+use minesweeper_sim::{Difficulty, Format, Options, Strategy};
+use rand::{rngs::StdRng, SeedableRng};
+
+fn parse(args: &str) -> anyhow::Result<Options> {
+    Options::parse(args.split_whitespace().map(String::from))
+}
+
+#[test]
+fn options() {
+    let options = parse("").unwrap();
+    assert_eq!(options.boards, 100);
+    assert_eq!(options.strategies, Strategy::ALL);
+    // 3 sizes at 3 levels, and the 3 classic boards
+    assert_eq!(options.difficulties.len(), 12);
+    assert_eq!(options.format, Format::Json);
+
+    let options =
+        parse("-n 5 --strategy=solver,random --difficulty small-hard --format csv").unwrap();
+    assert_eq!(options.boards, 5);
+    assert_eq!(options.strategies, [Strategy::Solver, Strategy::Random]);
+    assert_eq!(options.difficulties[0].name, "small-hard");
+    assert_eq!(options.difficulties[0].map_size, (20, 15));
+    assert_eq!(options.format, Format::Csv);
+
+    assert!(parse("--strategy cheat").is_err());
+    assert!(parse("--difficulty tiny-easy").is_err());
+    assert!(parse("--format xml").is_err());
+    assert!(parse("--boards 0").is_err());
+}
+
+#[test]
+fn solver_clears_boards_without_guesses() {
+    //   0 1 2 3
+    // 0 . 1 * 1
+    // 1 . 1 1 1
+    // 2 . . . .
+    let board = Board::with_bombs(Grid::square(4, 3), [(2, 0)]);
+    let mut rng = StdRng::seed_from_u64(1);
+
+    assert!(minesweeper_sim::play(
+        board.clone(),
+        (0, 2),
+        Strategy::Solver,
+        &mut rng
+    ));
+    assert!(minesweeper_sim::play(
+        board,
+        (0, 2),
+        Strategy::Probability,
+        &mut rng
+    ));
+}
+
+#[test]
+fn strategies_play_the_same_boards() {
+    let options = Options {
+        boards: 20,
+        difficulties: vec![Difficulty {
+            name: "tiny".to_string(),
+            map_size: (8, 8),
+            bomb_cnt: 10,
+        }],
+        ..Options::default()
+    };
+    let summaries = minesweeper_sim::run(&options);
+
+    assert_eq!(summaries.len(), 3);
+    for summary in &summaries {
+        assert_eq!(summary.boards(), 20);
+        assert_eq!(summary.three_bv, summaries[0].three_bv);
+        assert!(summary.three_bv.windows(2).all(|w| w[0] <= w[1]));
+    }
+    let wins: Vec<u32> = summaries.iter().map(|s| s.wins).collect();
+    assert!(wins[0] < wins[1] && wins[1] <= wins[2], "{:?}", wins);
+
+    // the same seed gives the same boards and games
+    let again = minesweeper_sim::run(&options);
+    assert_eq!(again[2].wins, summaries[2].wins);
+    assert_eq!(again[2].three_bv, summaries[2].three_bv);
+}
+
+#[test]
+fn results_print_as_csv_and_json() {
+    let options = parse("-n 3 --difficulty expert --strategy solver").unwrap();
+    let summaries = minesweeper_sim::run(&options);
+
+    let csv = minesweeper_sim::to_csv(&summaries);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("difficulty,width,height,mines,strategy,boards,wins,win_rate,"));
+    assert!(lines[1].starts_with("expert,30,16,99,solver,3,"));
+
+    let json = minesweeper_sim::to_json(&summaries);
+    assert!(json.starts_with("[\n  {\"difficulty\": \"expert\", \"width\": 30,"));
+    assert!(json.contains("\"strategy\": \"solver\", \"boards\": 3,"));
+    assert!(json.ends_with("}\n]\n"));
+}
@@ -1,213 +1,65 @@
-use rand::prelude::*;
-use tile::{Tile, TileContent, TileCover};
+use std::{
+    collections::HashSet,
+    net::TcpListener,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
 
-mod tile {
-    use ratatui::style::{Color, Style};
+use anyhow::{bail, Result};
+use chrono::NaiveDate;
+use minesweeper_engine::{
+    Board, GameState, HexDirection, Neighbourhood, Outcome, Shape, Status, TileContent, TileCover,
+};
+use rand::{prelude::*, rngs::StdRng};
 
-    #[derive(Debug, Clone, Copy)]
-    pub enum TileContent {
-        Empty(u8),
-        Bomb,
-    }
-
-    #[derive(Debug, Clone, Copy)]
-    pub enum TileCover {
-        Empty,
-        QuestionMark,
-        FlagMark,
-    }
-
-    impl TileCover {
-        pub fn next_cover(&self) -> Self {
-            match self {
-                Self::Empty => Self::FlagMark,
-                Self::FlagMark => Self::QuestionMark,
-                Self::QuestionMark => Self::Empty,
-            }
-        }
-    }
-
-    #[derive(Debug, Clone, Copy)]
-    pub struct Tile {
-        pub content: TileContent,
-        pub cover: Option<TileCover>,
-    }
-
-    const TILE_EMPTY_COVER_BG_COLOR: Color = Color::Rgb(180, 180, 180);
-    const TILE_QUESTION_MARK_COVER_BG_COLOR: Color = Color::Rgb(200, 200, 180);
-    const TILE_FLAG_MARK_COVER_BG_COLOR: Color = Color::Rgb(200, 180, 180);
-
-    const TILE_BOMB_CONTENT_BG_COLOR: Color = Color::Rgb(250, 200, 200);
-    const TILE_EMPTY_CONTENT_NUM_BG_COLORS: [Color; 9] = [
-        Color::Gray,
-        Color::LightBlue,
-        Color::LightRed,
-        Color::LightGreen,
-        Color::LightMagenta,
-        Color::LightCyan,
-        Color::LightYellow,
-        Color::LightBlue,
-        Color::LightRed,
-    ];
-
-    impl Tile {
-        pub fn symbol_n_style<'a>(&self) -> (&'a str, Style) {
-            match &self.cover {
-                Some(cover) => match cover {
-                    TileCover::Empty => (
-                        "ㅁ",
-                        Style::default()
-                            .bg(TILE_EMPTY_COVER_BG_COLOR)
-                            .fg(Color::White),
-                    ),
-                    TileCover::QuestionMark => (
-                        " ?",
-                        Style::default()
-                            .bg(TILE_QUESTION_MARK_COVER_BG_COLOR)
-                            .fg(Color::Yellow),
-                    ),
-                    TileCover::FlagMark => (
-                        " ⚑",
-                        Style::default()
-                            .bg(TILE_FLAG_MARK_COVER_BG_COLOR)
-                            .fg(Color::Red),
-                    ),
-                },
-                None => match self.content {
-                    TileContent::Empty(num) => {
-                        let s = match num {
-                            1 => " 1",
-                            2 => " 2",
-                            3 => " 3",
-                            4 => " 4",
-                            5 => " 5",
-                            6 => " 6",
-                            7 => " 7",
-                            8 => " 8",
-                            _ => " .",
-                        };
-                        (
-                            s,
-                            Style::default()
-                                .bg(TILE_EMPTY_CONTENT_NUM_BG_COLORS[num as usize])
-                                .fg(Color::Black),
-                        )
-                    }
-                    TileContent::Bomb => (
-                        " *",
-                        Style::default()
-                            .bg(TILE_BOMB_CONTENT_BG_COLOR)
-                            .fg(Color::Red),
-                    ),
-                },
-            }
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy, Default)]
-pub enum MapSize {
-    #[default]
-    Large,
-    Normal,
-    Small,
-}
+use crate::{
+    board_file,
+    coop::{self, Coop, CoopEvent},
+    daily::{self, Calendar, DailyLog},
+    describe,
+    editor::{self, Check, Editor},
+    effects::{self, Effects, Feedback},
+    mode::{self, GameMode},
+    net::{Message, Peer},
+    puzzle::{Goal, PuzzlePack, PuzzleProgress, PuzzleRun},
+    race::{Race, Winner},
+    replay::{Action, Replay},
+    settings::{Field, Settings},
+    stats::GameResult,
+    theme::{Glyphs, Theme, Zoom},
+    watch::{Spectators, View, Watch},
+};
 
-impl MapSize {
-    pub fn up(&self) -> MapSize {
-        match self {
-            MapSize::Large => MapSize::Small,
-            MapSize::Normal => MapSize::Large,
-            MapSize::Small => MapSize::Normal,
-        }
-    }
-
-    pub fn down(&self) -> MapSize {
-        match self {
-            MapSize::Large => MapSize::Normal,
-            MapSize::Normal => MapSize::Small,
-            MapSize::Small => MapSize::Large,
-        }
-    }
-
-    pub fn map_size(&self) -> (u16, u16) {
-        match self {
-            Self::Large => (50, 40),
-            Self::Normal => (40, 30),
-            Self::Small => (20, 15),
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy, Default)]
-pub enum GameLevel {
-    #[default]
-    Hard,
-    Normal,
-    Easy,
-}
-
-impl GameLevel {
-    pub fn up(&self) -> GameLevel {
-        match self {
-            GameLevel::Hard => GameLevel::Easy,
-            GameLevel::Normal => GameLevel::Hard,
-            GameLevel::Easy => GameLevel::Normal,
-        }
-    }
-
-    pub fn down(&self) -> GameLevel {
-        match self {
-            GameLevel::Hard => GameLevel::Normal,
-            GameLevel::Normal => GameLevel::Easy,
-            GameLevel::Easy => GameLevel::Hard,
-        }
-    }
-
-    pub fn bomb_cnt(&self, map_size: MapSize) -> u16 {
-        match map_size {
-            MapSize::Large => match self {
-                Self::Hard => 500,
-                Self::Normal => 300,
-                Self::Easy => 200,
-            },
-            MapSize::Normal => match self {
-                Self::Hard => 300,
-                Self::Normal => 200,
-                Self::Easy => 100,
-            },
-            MapSize::Small => match self {
-                Self::Hard => 75,
-                Self::Normal => 35,
-                Self::Easy => 15,
-            },
-        }
-    }
-}
+pub use minesweeper_engine::presets::{mines_at_density, GameLevel, MapSize};
 
 #[derive(Debug, Clone, Copy, Default)]
 pub enum MenuKind {
     #[default]
     MapSize,
     GameLevel,
+    /// the entry opening the settings screen
+    Settings,
 }
 
 impl MenuKind {
     pub fn left(&self) -> MenuKind {
         match self {
-            MenuKind::MapSize => MenuKind::GameLevel,
+            MenuKind::MapSize => MenuKind::Settings,
             MenuKind::GameLevel => MenuKind::MapSize,
+            MenuKind::Settings => MenuKind::GameLevel,
         }
     }
 
     pub fn right(&self) -> MenuKind {
         match self {
             MenuKind::MapSize => MenuKind::GameLevel,
-            MenuKind::GameLevel => MenuKind::MapSize,
+            MenuKind::GameLevel => MenuKind::Settings,
+            MenuKind::Settings => MenuKind::MapSize,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy)]
 pub enum Movement {
     Up,
     Down,
@@ -215,18 +67,50 @@ pub enum Movement {
     Right,
 }
 
-const DXDY8: [(i32, i32); 8] = [
-    (-1, -1),
-    (-1, 0),
-    (-1, 1),
-    (0, 1),
-    (1, 1),
-    (1, 0),
-    (1, -1),
-    (0, -1),
-];
+/// A replay being played back.
+#[derive(Debug)]
+pub struct Playback {
+    pub replay: Replay,
+    /// index of the next event to apply
+    pub next: usize,
+}
 
-const DXDY4: [(i32, i32); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+/// The part of the board that was drawn and where, in terminal cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoardView {
+    /// cell of the first drawn tile
+    pub origin: (u16, u16),
+    /// first drawn tile
+    pub scroll: (u16, u16),
+    /// drawn tiles across and down
+    pub tiles: (u16, u16),
+    /// cells of one tile across and down, see [`Zoom::tile_size`]
+    pub tile_size: (u16, u16),
+    /// odd rows are drawn half a tile to the right
+    pub hex: bool,
+}
+
+impl BoardView {
+    /// The tile drawn at a terminal cell, if any.
+    pub fn tile_at(&self, column: u16, row: u16) -> Option<(u16, u16)> {
+        let (tile_w, tile_h) = self.tile_size;
+        let dy = row.checked_sub(self.origin.1)? / tile_h;
+        if dy >= self.tiles.1 {
+            return None;
+        }
+        let y = self.scroll.1 + dy;
+        let shift = if self.hex && y % 2 == 1 {
+            tile_w / 2
+        } else {
+            0
+        };
+        let dx = column.checked_sub(self.origin.0 + shift)? / tile_w;
+        if dx >= self.tiles.0 {
+            return None;
+        }
+        Some((self.scroll.0 + dx, y))
+    }
+}
 
 /// Application.
 #[derive(Debug, Default)]
@@ -240,13 +124,102 @@ pub struct App {
     pub menu_map_size: MapSize,
     pub menu_game_level: GameLevel,
 
-    pub over: bool,
-
-    pub map_size: (u16, u16), //(w,h)
-    pub bomb_cnt: u16,
-    pub empty_cnt: u16,
+    pub game: GameState,
     pub curr_pos: (u16, u16), //(x,y)
-    pub mine_map: Vec<Vec<Tile>>,
+    /// vi-style count prefix typed before a movement key
+    pub move_count: Option<u16>,
+
+    pub theme: Theme,
+    pub glyphs: Glyphs,
+    /// fixed seed for every new board, random boards if `None`
+    pub seed: Option<u64>,
+    /// seed the current board was generated from
+    pub game_seed: u64,
+    /// generate boards that can be cleared without guessing
+    pub no_guess: bool,
+    /// tile shape of new boards
+    pub shape: Shape,
+    /// new boards wrap around their edges (torus)
+    pub wrap: bool,
+    /// neighbours of a tile on new boards
+    pub neighbourhood: Neighbourhood,
+    /// layers of new boards, 1 for flat boards
+    pub layers: u16,
+    /// most mines on one tile of new boards, 1 for classic boards
+    pub max_mines: u8,
+    /// new boards move a mine away from the first uncovered tile
+    pub safe_first_click: bool,
+    /// ask before quitting a game in progress
+    pub confirm_quit: bool,
+    /// the quit question is shown
+    pub quitting: bool,
+    /// flag toggles go through the question mark on new boards
+    pub question_marks: bool,
+    /// the uncover key (and left click) places flags instead
+    pub flag_mode: bool,
+    /// where the board was last drawn, to find the tile under the mouse
+    pub board_view: Option<BoardView>,
+    /// how big tiles are drawn
+    pub zoom: Zoom,
+    /// rules of new games
+    pub mode: GameMode,
+    /// lives of new [`GameMode::Lives`] games
+    pub lives: u32,
+    /// time the current time attack game has, openings included
+    pub time_limit: Option<Duration>,
+
+    /// preferences kept in the config file
+    pub settings: Settings,
+    /// the settings screen and its selected row, if open
+    pub settings_select: Option<usize>,
+    /// settings were changed since the last save
+    pub settings_unsaved: bool,
+
+    /// sound cues and visual effects
+    pub effects: Effects,
+
+    /// announce the tile under the cursor in a status line, for screen readers
+    pub accessible: bool,
+    /// description of the tiles around the cursor, until the next key
+    pub description: Option<Vec<String>>,
+
+    /// the daily calendar screen, if open
+    pub calendar: Option<Calendar>,
+    /// results of the daily boards
+    pub daily_log: DailyLog,
+    /// date of the daily board being played
+    pub daily: Option<NaiveDate>,
+
+    /// puzzles to choose from
+    pub puzzle_pack: PuzzlePack,
+    /// which puzzles were solved
+    pub puzzle_progress: PuzzleProgress,
+    /// the puzzle list screen and its selected puzzle, if open
+    pub puzzle_select: Option<usize>,
+    /// the puzzle being played
+    pub puzzle: Option<PuzzleRun>,
+
+    /// race against another instance, if connected
+    pub race: Option<Race>,
+    /// board shared with other players, if connected
+    pub coop: Option<Coop>,
+    /// spectators following our games, if published
+    pub spectators: Option<Spectators>,
+    /// the game of another instance, followed read-only
+    pub watch: Option<Watch>,
+    /// the board editor, if open; the edited board is the game board
+    pub editor: Option<Editor>,
+
+    pub started_at: Option<Instant>,
+    pub recording: Option<Replay>,
+    pub playback: Option<Playback>,
+    /// result of the last finished game, until someone takes it
+    pub result: Option<GameResult>,
+    /// text of an exported board, until someone writes it out
+    pub export: Option<String>,
+    /// something that went wrong without stopping the game, like a file that
+    /// could not be written; shown until the next key
+    pub warning: Option<String>,
 }
 
 impl App {
@@ -257,12 +230,27 @@ impl App {
             menu_focus: MenuKind::MapSize,
             menu_map_size: MapSize::Normal,
             menu_game_level: GameLevel::Normal,
+            max_mines: 1,
+            layers: 1,
+            question_marks: true,
+            lives: mode::DEFAULT_LIVES,
+            puzzle_pack: PuzzlePack::builtin(),
             ..Self::default()
         }
     }
 
     /// Handles the tick event of the terminal.
-    pub fn tick(&self) {}
+    pub fn tick(&mut self) {
+        self.effects.tick();
+        self.check_time();
+        self.play_due_events();
+        if let Some(race) = &mut self.race {
+            race.poll();
+        }
+        self.poll_coop();
+        self.poll_watch();
+        self.publish_view();
+    }
 
     /// Set running to false to quit the application.
     pub fn quit(&mut self) {
@@ -273,9 +261,15 @@ impl App {
         match movement {
             Movement::Left => {
                 self.menu_focus = self.menu_focus.left();
+                if self.menu_level_fixed() {
+                    self.menu_focus = self.menu_focus.left();
+                }
             }
             Movement::Right => {
                 self.menu_focus = self.menu_focus.right();
+                if self.menu_level_fixed() {
+                    self.menu_focus = self.menu_focus.right();
+                }
             }
             Movement::Up => match self.menu_focus {
                 MenuKind::MapSize => {
@@ -284,6 +278,7 @@ impl App {
                 MenuKind::GameLevel => {
                     self.menu_game_level = self.menu_game_level.up();
                 }
+                MenuKind::Settings => {}
             },
             Movement::Down => match self.menu_focus {
                 MenuKind::MapSize => {
@@ -292,181 +287,972 @@ impl App {
                 MenuKind::GameLevel => {
                     self.menu_game_level = self.menu_game_level.down();
                 }
+                MenuKind::Settings => {}
             },
         }
     }
 
-    fn init_members(&mut self, map_size: (u16, u16), bomb_cnt: u16) {
-        self.map_size = map_size;
-        self.bomb_cnt = bomb_cnt;
-        self.empty_cnt = map_size.0 * map_size.1 - bomb_cnt;
-        self.curr_pos = (map_size.0 / 2 - 1, map_size.1 / 2 - 1);
-        self.over = false;
+    /// The focus is on the game level, which a classic size picks by itself.
+    fn menu_level_fixed(&self) -> bool {
+        matches!(self.menu_focus, MenuKind::GameLevel) && self.menu_map_size.level().is_some()
+    }
+
+    /// The level of the menu board: the picked one, or that of a classic size.
+    pub fn menu_level(&self) -> GameLevel {
+        self.menu_map_size.level().unwrap_or(self.menu_game_level)
+    }
+
+    /// Size and mines of the board picked in the menu, every layer at the level's density.
+    pub fn menu_board(&self) -> ((u16, u16), u32) {
+        let (width, height) = self.menu_map_size.map_size();
+        let tiles = width as u64 * height as u64 * self.layers.max(1) as u64;
+        let bomb_cnt = mines_at_density(tiles, self.menu_level().density());
+        ((width, height), bomb_cnt)
+    }
+
+    /// Switches the rules of new games to the next mode.
+    pub fn next_mode(&mut self) {
+        self.mode = self.mode.next();
+    }
+
+    /// The game ended, or the race it belongs to was decided.
+    pub fn over(&self) -> bool {
+        self.game.is_over()
+            || self.race.as_ref().is_some_and(|race| race.winner.is_some())
+            || self.coop.as_ref().is_some_and(|coop| coop.host_left)
+            || self.watch.as_ref().is_some_and(|watch| watch.left)
+            || self.puzzle.is_some_and(|puzzle| puzzle.solved)
+    }
+
+    pub fn map_size(&self) -> (u16, u16) {
+        self.game.board().size()
+    }
+
+    pub fn reset(&mut self) {
+        self.game = GameState::default();
+        self.menu = true;
+        self.playback = None;
+        self.daily = None;
+        self.race = None;
+        self.coop = None;
+        self.watch = None;
+        self.editor = None;
+        self.puzzle = None;
+        self.description = None;
+        self.effects.active = None;
+        self.quitting = false;
+        self.time_limit = None;
+    }
+
+    /// Builds the board described by the parameters of `setup` and starts playing it.
+    fn start_game(&mut self, setup: &Replay) {
+        let (width, height) = setup.map_size;
+        let layers = setup.layers.max(1);
+        let grid = setup.grid();
+        let mut rng = StdRng::seed_from_u64(setup.seed);
+        // the middle of the middle layer
+        let start = (
+            width.max(2) / 2 - 1,
+            layers / 2 * height + height.max(2) / 2 - 1,
+        );
+
+        let board = if setup.no_guess {
+            Board::generate_no_guess(grid, setup.bomb_cnt, &mut rng, start)
+        } else {
+            Board::generate_stacked(grid, setup.bomb_cnt, setup.max_mines, &mut rng, None)
+        };
+
+        let lives = match setup.mode {
+            GameMode::Lives => setup.lives,
+            _ => 1,
+        };
+        self.game = GameState::new(board).with_lives(lives);
+        self.time_limit =
+            (setup.mode == GameMode::TimeAttack).then(|| mode::time_limit(self.game.empty_cnt()));
+        self.game_seed = setup.seed;
+        self.curr_pos = start;
         self.menu = false;
+        self.calendar = None;
+        self.puzzle = None;
+        self.started_at = Some(Instant::now());
+        self.result = None;
+
+        // in no-guess mode the start tile is an opening, so open it up front
+        if setup.no_guess {
+            self.game.reveal(start.0, start.1);
+        }
     }
 
-    fn init_map(&mut self) {
-        let (width, height) = self.map_size;
-        let bomb_cnt = self.bomb_cnt;
-        let mut rng = rand::thread_rng();
-        let mut positions = vec![];
+    /// Parameters of a new board with the player's options.
+    fn new_setup(&self, map_size: (u16, u16), bomb_cnt: u32) -> Replay {
+        let seed = self.seed.unwrap_or_else(|| rand::thread_rng().gen());
+        let mut setup = Replay::new(map_size, bomb_cnt, seed, self.no_guess);
+        setup.shape = self.shape;
+        setup.wrap = self.wrap;
+        setup.neighbourhood = self.neighbourhood;
+        setup.layers = self.layers;
+        setup.max_mines = self.max_mines;
+        setup.safe_first = self.safe_first_click;
+        setup.question_marks = self.question_marks;
+        setup.mode = self.mode;
+        setup.lives = self.lives;
+        setup
+    }
 
-        self.mine_map.clear();
+    pub fn init_mine_map(&mut self, map_size: (u16, u16), bomb_cnt: u32) {
+        let recording = self.new_setup(map_size, bomb_cnt);
+        self.start_game(&recording);
+        self.playback = None;
+        self.daily = None;
+        self.recording = Some(recording);
+    }
 
-        for y in 0..height {
-            self.mine_map.push(vec![]);
+    /// Starts the daily board of a date: a classic board of a fixed size and level,
+    /// ignoring the board options of the player.
+    pub fn start_daily(&mut self, date: NaiveDate) {
+        let map_size = daily::DAILY_SIZE.map_size();
+        let recording = Replay::new(map_size, daily::DAILY_MINES, daily::seed(date), false);
+        self.start_game(&recording);
+        self.playback = None;
+        self.daily = Some(date);
+        self.recording = Some(recording);
+    }
+
+    /// Hosts a race: sends the board to the connected peer and starts it here too.
+    pub fn host_race(&mut self, mut peer: Peer, map_size: (u16, u16), bomb_cnt: u32) -> Result<()> {
+        let mut setup = self.new_setup(map_size, bomb_cnt);
+        // both players get the same board, whatever they uncover first
+        setup.safe_first = false;
+        // the board message does not carry the game mode
+        setup.mode = GameMode::Classic;
+        peer.send(&Message::Board(setup.clone()))?;
+        self.start_race(peer, setup);
+        Ok(())
+    }
 
-            for x in 0..width {
-                let size = self.mine_map.len();
+    /// Joins a race: waits for the host to send the board.
+    pub fn join_race(&mut self, peer: Peer) -> Result<()> {
+        loop {
+            match peer.recv()? {
+                Message::Board(setup) => {
+                    self.start_race(peer, setup);
+                    return Ok(());
+                }
+                Message::Bye => bail!("the host left without sending a playable board"),
+                _ => {}
+            }
+        }
+    }
 
-                self.mine_map[size - 1].push(Tile {
-                    content: TileContent::Empty(0),
-                    // cover: Some(TileCover::FlagMark),
-                    // cover: Some(TileCover::QuestionMark),
-                    cover: Some(TileCover::Empty),
-                    // cover: None,
-                });
+    fn start_race(&mut self, peer: Peer, setup: Replay) {
+        self.start_game(&setup);
+        self.playback = None;
+        self.daily = None;
+        self.recording = Some(setup);
+        self.race = Some(Race::new(peer));
+    }
 
-                positions.push((x, y));
+    /// Hosts a shared board; other players join on `listener` while it is played.
+    pub fn host_coop(&mut self, listener: TcpListener, map_size: (u16, u16), bomb_cnt: u32) {
+        let mut setup = self.new_setup(map_size, bomb_cnt);
+        // the board message does not carry the game mode
+        setup.mode = GameMode::Classic;
+        self.start_game(&setup);
+        self.playback = None;
+        self.daily = None;
+        self.recording = Some(setup.clone());
+        let mut coop = Coop::host(listener, setup);
+        let (x, y) = self.curr_pos;
+        coop.broadcast(coop::HOST, Action::Move(x, y));
+        self.coop = Some(coop);
+    }
+
+    /// Joins a board shared by a host.
+    pub fn join_coop(&mut self, peer: Peer) -> Result<()> {
+        let (mut coop, setup) = Coop::join(peer)?;
+        self.start_game(&setup);
+        self.playback = None;
+        self.daily = None;
+        self.recording = Some(setup);
+        let (x, y) = self.curr_pos;
+        coop.request(Action::Move(x, y), None);
+        self.coop = Some(coop);
+        Ok(())
+    }
+
+    /// Applies what the other co-op players did.
+    fn poll_coop(&mut self) {
+        let Some(coop) = &mut self.coop else {
+            return;
+        };
+        for event in coop.poll() {
+            match event {
+                CoopEvent::Request {
+                    player,
+                    action: action @ Action::Move(x, y),
+                    ..
+                } => {
+                    let on_board = self.game.board().in_bounds(x as i32, y as i32);
+                    if let Some(coop) = self.coop.as_mut().filter(|_| on_board) {
+                        coop.broadcast(player, action);
+                    }
+                }
+                CoopEvent::Request {
+                    player,
+                    action: action @ (Action::Uncover(x, y) | Action::ChangeCover(x, y)),
+                    seen,
+                } => {
+                    // a request made on a tile that changed since is dropped
+                    let board = self.game.board();
+                    if board.in_bounds(x as i32, y as i32) && board.tile(x, y).cover == seen {
+                        self.apply(player, action);
+                    }
+                }
+                CoopEvent::Apply {
+                    player,
+                    action: action @ (Action::Uncover(x, y) | Action::ChangeCover(x, y)),
+                } => {
+                    // the host knows the board, a tile off it means it cannot be trusted
+                    if self.game.board().in_bounds(x as i32, y as i32) {
+                        self.apply(player, action);
+                    } else if let Some(coop) = &mut self.coop {
+                        coop.hang_up();
+                        return;
+                    }
+                }
+                CoopEvent::Apply { player, action } => self.apply(player, action),
             }
         }
+    }
 
-        positions.shuffle(&mut rng);
+    /// Lets spectators join on `listener` and follow every game played from now on.
+    pub fn publish(&mut self, listener: TcpListener) {
+        self.spectators = Some(Spectators::listen(listener));
+    }
 
-        for i in 0..bomb_cnt {
-            let (x, y) = positions[i as usize];
-            self.mine_map[y as usize][x as usize].content = TileContent::Bomb;
+    /// Sends the game to the spectators, unless we are in a menu.
+    fn publish_view(&mut self) {
+        let in_game = !self.menu
+            && self.watch.is_none()
+            && self.editor.is_none()
+            && !self.game.board().tiles().is_empty();
+        if self.spectators.is_none() || !in_game {
+            return;
+        }
+        let last = self.spectators.as_ref().and_then(|s| s.last.as_ref());
+        // the clock of a finished game stops where it was last seen running
+        let secs = match last {
+            Some(last) if self.game.is_over() => last.secs,
+            _ => self.elapsed().as_secs(),
+        };
+        let time_left = self.time_left().map(|left| left.as_secs());
+        let view = View::new(&self.game, self.curr_pos, secs, time_left);
+        if let Some(spectators) = &mut self.spectators {
+            spectators.publish(view);
         }
+    }
 
-        for i in 0..bomb_cnt {
-            let (x, y) = positions[i as usize];
-            let x = x as i32;
-            let y = y as i32;
+    /// Follows the game published by the instance at the other end of `peer`.
+    pub fn watch(&mut self, peer: Peer) -> Result<()> {
+        self.watch = Some(Watch::join(peer)?);
+        self.show_view();
+        Ok(())
+    }
 
-            for (dx, dy) in DXDY8 {
-                let (new_x, new_y) = (x + dx, y + dy);
+    fn poll_watch(&mut self) {
+        if self.watch.as_mut().is_some_and(|watch| watch.poll()) {
+            self.show_view();
+        }
+    }
 
-                if !(0 <= new_x && new_x < width as i32 && 0 <= new_y && new_y < height as i32) {
-                    continue;
-                }
+    /// Puts the watched game on the board.
+    fn show_view(&mut self) {
+        let Some(watch) = &self.watch else {
+            return;
+        };
+        self.game = GameState::new(watch.view.board());
+        self.curr_pos = watch.view.cursor;
+        self.menu = false;
+        self.playback = None;
+        self.recording = None;
+    }
 
-                let tile = &mut self.mine_map[new_y as usize][new_x as usize];
-                if let TileContent::Empty(num) = tile.content {
-                    tile.content = TileContent::Empty(num + 1);
-                }
+    /// Starts playing a board loaded from a file.
+    ///
+    /// There is no seed to rebuild it from, so the game is neither recorded
+    /// nor counted in the statistics.
+    pub fn load_board(&mut self, board: Board) {
+        let (width, height) = board.size();
+        self.game = GameState::new(board);
+        self.time_limit = None;
+        self.game_seed = 0;
+        self.curr_pos = (width.max(2) / 2 - 1, height.max(2) / 2 - 1);
+        self.menu = false;
+        self.calendar = None;
+        self.puzzle = None;
+        self.started_at = Some(Instant::now());
+        self.result = None;
+        self.playback = None;
+        self.daily = None;
+        self.recording = None;
+    }
+
+    /// A board without mines, with the player's grid options.
+    pub fn empty_board(&self, map_size: (u16, u16)) -> Board {
+        Board::new(self.new_setup(map_size, 0).grid())
+    }
+
+    /// Opens the editor on the mines of `board`, saving to `path` (see [`Editor::path`]).
+    pub fn open_editor(&mut self, board: Board, path: Option<PathBuf>) {
+        self.load_board(editor::uncovered(board));
+        self.started_at = None;
+        self.editor = Some(Editor::new(path));
+    }
+
+    /// Adds a mine under the cursor or takes it off.
+    pub fn editor_toggle_mine(&mut self) {
+        let Some(editor) = &mut self.editor else {
+            return;
+        };
+        let (x, y) = self.curr_pos;
+        self.game = GameState::new(editor::toggle_mine(self.game.board(), x, y));
+        editor.check = None;
+        editor.saved = false;
+    }
+
+    /// Asks the solver whether the edited board can be cleared without guessing.
+    pub fn editor_check(&mut self) {
+        let Some(editor) = &mut self.editor else {
+            return;
+        };
+        editor.check = Some(match self.game.board().solvable_start() {
+            Some(start) => Check::Solvable(start),
+            None => Check::NeedsGuessing,
+        });
+    }
+
+    /// Saves the edited board, see [`board_file::to_text`].
+    pub fn editor_save(&mut self) {
+        let Some(editor) = &mut self.editor else {
+            return;
+        };
+        editor.save = Some(board_file::to_text(self.game.board()));
+        editor.check = None;
+        editor.saved = true;
+    }
+
+    /// Leaves the editor and plays the edited board, from the tile the solver
+    /// found if it was checked.
+    pub fn editor_play(&mut self) {
+        let Some(editor) = self.editor.take() else {
+            return;
+        };
+        let mut board = self.game.board().clone();
+        for tile in board.tiles_mut() {
+            tile.cover = Some(TileCover::Empty);
+        }
+        self.load_board(board);
+        if let Some(Check::Solvable(start)) = editor.check {
+            self.curr_pos = start;
+        }
+    }
+
+    /// Opens the puzzle list on the first unsolved puzzle.
+    pub fn open_puzzles(&mut self) {
+        let pack = &self.puzzle_pack;
+        let first_unsolved = pack
+            .puzzles
+            .iter()
+            .position(|puzzle| !self.puzzle_progress.is_solved(pack, puzzle));
+        self.puzzle_select = Some(first_unsolved.unwrap_or(0));
+    }
+
+    pub fn close_puzzles(&mut self) {
+        self.puzzle_select = None;
+    }
+
+    pub fn puzzle_select_move(&mut self, movement: Movement) {
+        let last = self.puzzle_pack.puzzles.len().saturating_sub(1);
+        if let Some(selected) = &mut self.puzzle_select {
+            match movement {
+                Movement::Up => *selected = selected.saturating_sub(1),
+                Movement::Down => *selected = (*selected + 1).min(last),
+                _ => {}
             }
         }
     }
 
-    pub fn reset(&mut self) {
-        self.over = false;
-        self.menu = true;
+    /// Starts a puzzle of the pack, with its tiles uncovered and flagged as given.
+    pub fn start_puzzle(&mut self, index: usize) {
+        let Some(puzzle) = self.puzzle_pack.puzzles.get(index) else {
+            return;
+        };
+        self.load_board(puzzle.board.clone());
+        self.puzzle_select = None;
+        self.puzzle = Some(PuzzleRun {
+            index,
+            solved: false,
+        });
     }
 
-    pub fn init_mine_map(&mut self, map_size: (u16, u16), bomb_cnt: u16) {
-        self.init_members(map_size, bomb_cnt);
-        self.init_map();
+    /// Marks the puzzle solved once its goal is reached.
+    fn check_puzzle(&mut self, outcome: Outcome) {
+        let Some(run) = &mut self.puzzle else {
+            return;
+        };
+        let puzzle = &self.puzzle_pack.puzzles[run.index];
+        run.solved = match puzzle.goal {
+            Goal::Clear => outcome == Outcome::Won,
+            Goal::Find(x, y) => {
+                self.game.status() != Status::Lost && !self.game.board().tile(x, y).is_covered()
+            }
+        };
+        if run.solved {
+            self.puzzle_progress.solve(&self.puzzle_pack, puzzle);
+        }
     }
 
-    pub fn game_move(&mut self, movement: Movement) {
-        let (w, h) = self.map_size;
+    /// Takes over the settings for the game: theme, glyphs and so on.
+    pub fn apply_settings(&mut self) {
+        self.theme = self.settings.theme;
+        self.glyphs = self.settings.glyphs;
+        self.safe_first_click = self.settings.safe_first_click;
+        self.confirm_quit = self.settings.confirm_quit;
+        self.question_marks = self.settings.question_marks;
+        self.effects.settings = self.settings.effects;
+    }
+
+    pub fn open_settings(&mut self) {
+        self.settings_select = Some(0);
+    }
+
+    pub fn close_settings(&mut self) {
+        self.settings_select = None;
+    }
+
+    /// Moves the selection of the settings screen; the row after the fields resets them.
+    pub fn settings_move(&mut self, movement: Movement) {
+        if let Some(selected) = &mut self.settings_select {
+            match movement {
+                Movement::Up => *selected = selected.saturating_sub(1),
+                Movement::Down => *selected = (*selected + 1).min(Field::ALL.len()),
+                _ => {}
+            }
+        }
+    }
+
+    /// Changes the selected setting to its next (or previous) value, or resets
+    /// every setting on the reset row.
+    pub fn settings_change(&mut self, forward: bool) {
+        let Some(selected) = self.settings_select else {
+            return;
+        };
+        match Field::ALL.get(selected) {
+            Some(&field) => self.settings.cycle(field, forward),
+            None => self.settings = Settings::default(),
+        }
+        self.apply_settings();
+        self.settings_unsaved = true;
+    }
+
+    /// Quits, or first asks if a game is in progress and the player wants to be asked.
+    pub fn request_quit(&mut self) {
+        if self.confirm_quit && !self.menu && !self.over() && self.playback.is_none() {
+            self.quitting = true;
+        } else {
+            self.quit();
+        }
+    }
+
+    /// Describes the tile under the cursor and its neighbours in words.
+    pub fn describe_surroundings(&mut self) {
         let (x, y) = self.curr_pos;
-        match movement {
-            Movement::Up => self.curr_pos.1 = (y + h - 1) % h,
-            Movement::Down => self.curr_pos.1 = (y + 1) % h,
-            Movement::Left => self.curr_pos.0 = (x + w - 1) % w,
-            Movement::Right => self.curr_pos.0 = (x + 1) % w,
+        self.description = Some(describe::surroundings(self.game.board(), x, y));
+    }
+
+    /// Exports the mines of the current board, see [`board_file::to_text`].
+    pub fn export_board(&mut self) {
+        self.export = Some(board_file::to_text(self.game.board()));
+    }
+
+    /// Starts playing a recorded game back instead of taking player input.
+    pub fn start_playback(&mut self, replay: Replay) {
+        self.start_game(&replay);
+        self.recording = None;
+        self.daily = None;
+        self.playback = Some(Playback { replay, next: 0 });
+    }
+
+    /// Opens the daily calendar on the month of `today`.
+    pub fn open_calendar(&mut self, today: NaiveDate) {
+        self.calendar = Some(Calendar::new(today));
+    }
+
+    pub fn close_calendar(&mut self) {
+        self.calendar = None;
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.started_at.map_or(Duration::ZERO, |t| t.elapsed())
+    }
+
+    fn record(&mut self, action: Action) {
+        let ms = self.elapsed().as_millis() as u64;
+        if let Some(recording) = &mut self.recording {
+            recording.events.push((ms, action));
         }
     }
 
-    fn game_over(&mut self) {
-        for row in self.mine_map.iter_mut() {
-            for cell in row {
-                match cell.content {
-                    TileContent::Bomb => {
-                        cell.cover = None;
-                    }
-                    _ => {}
+    fn play_due_events(&mut self) {
+        let ms = self.elapsed().as_millis() as u64;
+        while let Some(playback) = &mut self.playback {
+            let Some(&(at, action)) = playback.replay.events.get(playback.next) else {
+                break;
+            };
+            if at > ms || self.game.is_over() {
+                break;
+            }
+            playback.next += 1;
+
+            match action {
+                Action::Move(x, y) => self.curr_pos = (x, y),
+                Action::Uncover(x, y) => {
+                    self.curr_pos = (x, y);
+                    self.uncover_tile();
+                }
+                Action::ChangeCover(x, y) => {
+                    self.curr_pos = (x, y);
+                    self.change_cover();
                 }
             }
         }
+    }
 
-        self.over = true;
+    fn finish(&mut self) {
+        let Some(setup) = &self.recording else {
+            return;
+        };
+
+        let (w, h) = setup.map_size;
+        let key = if self.daily.is_some() {
+            "daily".to_string()
+        } else {
+            format!(
+                "{}x{}{}/{}{}{}{}{}{}{}{}{}",
+                w,
+                h,
+                if setup.layers > 1 {
+                    format!("x{}", setup.layers)
+                } else {
+                    String::new()
+                },
+                setup.bomb_cnt,
+                if setup.max_mines > 1 {
+                    format!(" stack{}", setup.max_mines)
+                } else {
+                    String::new()
+                },
+                if setup.shape == Shape::Hex {
+                    " hex"
+                } else {
+                    ""
+                },
+                if setup.wrap { " torus" } else { "" },
+                match setup.neighbourhood {
+                    Neighbourhood::Standard => String::new(),
+                    neighbourhood => format!(" {}", neighbourhood.name()),
+                },
+                if setup.no_guess { " no-guess" } else { "" },
+                if self.race.is_some() { " race" } else { "" },
+                if self.coop.is_some() { " coop" } else { "" },
+                match setup.mode {
+                    GameMode::Classic => String::new(),
+                    GameMode::Lives => format!(" lives{}", setup.lives),
+                    GameMode::TimeAttack => " time-attack".to_string(),
+                }
+            )
+        };
+        self.result = Some(GameResult {
+            key,
+            won: self.game.status() == Status::Won,
+            time: self.elapsed(),
+            daily: self.daily,
+        });
     }
 
-    fn uncover_chaining(&mut self, x: u16, y: u16, go: bool) -> u32 {
-        let (width, height) = self.map_size;
-        let mut cnt = 1;
+    /// Records a cursor move made by the player.
+    pub fn moved(&mut self) {
+        let (x, y) = self.curr_pos;
+        self.record(Action::Move(x, y));
+        if let Some(coop) = &mut self.coop {
+            if coop.is_host() {
+                coop.broadcast(coop.me, Action::Move(x, y));
+            } else {
+                coop.request(Action::Move(x, y), None);
+            }
+        }
+    }
+
+    pub fn push_move_count(&mut self, digit: u16) {
+        let count = self.move_count.unwrap_or(0);
+        self.move_count = Some(count.saturating_mul(10).saturating_add(digit));
+    }
+
+    fn take_move_count(&mut self) -> u16 {
+        self.move_count.take().unwrap_or(1).max(1)
+    }
 
-        self.mine_map[y as usize][x as usize].cover = None;
+    /// First row and height of the layer the cursor is on, the whole board if it is flat.
+    fn cursor_layer(&self) -> (u16, u16) {
+        let grid = self.game.board().grid();
+        let height = grid.layer_height();
+        (grid.layer(self.curr_pos.1) * height, height)
+    }
 
-        if !go {
-            return cnt;
+    /// Moves the cursor, wrapping around within its layer.
+    pub fn game_move(&mut self, movement: Movement) {
+        let (w, _) = self.map_size();
+        let (top, h) = self.cursor_layer();
+        let (w, h) = (w as u32, h as u32);
+        let (x, y) = (self.curr_pos.0 as u32, (self.curr_pos.1 - top) as u32);
+        let n = self.take_move_count() as u32;
+        match movement {
+            Movement::Up => self.curr_pos.1 = top + ((y + h - n % h) % h) as u16,
+            Movement::Down => self.curr_pos.1 = top + ((y + n) % h) as u16,
+            Movement::Left => self.curr_pos.0 = ((x + w - n % w) % w) as u16,
+            Movement::Right => self.curr_pos.0 = ((x + n) % w) as u16,
         }
+    }
 
-        for (dx, dy) in DXDY4 {
-            let (new_x, new_y) = (x as i32 + dx, y as i32 + dy);
+    /// Steps to the neighbouring hex tile in a direction, stopping at the board edge.
+    pub fn game_move_hex(&mut self, direction: HexDirection) {
+        let grid = *self.game.board().grid();
+        for _ in 0..self.take_move_count() {
+            let (x, y) = self.curr_pos;
+            match grid.hex_step(x, y, direction) {
+                Some(pos) => self.curr_pos = pos,
+                None => break,
+            }
+        }
+    }
 
-            if new_x < 0 || new_x >= width as i32 || new_y < 0 || new_y >= height as i32 {
-                continue;
+    /// Moves the cursor to the same tile on the layer above (or below) of a
+    /// three-dimensional board, stopping at the first and last layer.
+    pub fn game_move_layer(&mut self, up: bool) {
+        let grid = *self.game.board().grid();
+        let height = grid.layer_height();
+        for _ in 0..self.take_move_count() {
+            let layer = grid.layer(self.curr_pos.1);
+            if up && layer > 0 {
+                self.curr_pos.1 -= height;
+            } else if !up && layer + 1 < grid.layers {
+                self.curr_pos.1 += height;
             }
+        }
+    }
 
-            let tile = self.mine_map[new_y as usize][new_x as usize];
+    /// Jumps to the board (or layer) edge in the given direction.
+    /// With a count prefix, `Up`/`Down` jump to that (1-based) row instead, like vi's `5G`.
+    pub fn game_jump_edge(&mut self, movement: Movement) {
+        let (w, _) = self.map_size();
+        let (top, h) = self.cursor_layer();
+        let row = self.move_count.take().map(|n| n.clamp(1, h) - 1);
+        match movement {
+            Movement::Up => self.curr_pos.1 = top + row.unwrap_or(0),
+            Movement::Down => self.curr_pos.1 = top + row.unwrap_or(h - 1),
+            Movement::Left => self.curr_pos.0 = 0,
+            Movement::Right => self.curr_pos.0 = w - 1,
+        }
+    }
 
-            if let None = tile.cover {
-                continue;
+    /// Jumps to the next covered, unflagged tile in the given direction
+    /// (no wrapping, never to another layer).
+    pub fn game_jump_covered(&mut self, movement: Movement) {
+        let grid = *self.game.board().grid();
+        let layer = grid.layer(self.curr_pos.1);
+        let (dx, dy) = match movement {
+            Movement::Up => (0, -1),
+            Movement::Down => (0, 1),
+            Movement::Left => (-1, 0),
+            Movement::Right => (1, 0),
+        };
+
+        for _ in 0..self.take_move_count() {
+            let (mut x, mut y) = (self.curr_pos.0 as i32, self.curr_pos.1 as i32);
+            loop {
+                (x, y) = (x + dx, y + dy);
+                if !grid.in_bounds(x, y) || grid.layer(y as u16) != layer {
+                    return;
+                }
+                if self.is_open_cover(x as u16, y as u16) {
+                    self.curr_pos = (x as u16, y as u16);
+                    break;
+                }
             }
+        }
+    }
 
-            if let TileContent::Empty(n) = tile.content {
-                cnt += if n == 0 {
-                    self.uncover_chaining(new_x as u16, new_y as u16, true)
-                } else {
-                    self.uncover_chaining(new_x as u16, new_y as u16, false)
-                };
+    /// Jumps to the next (or previous) frontier tile in reading order, wrapping around.
+    ///
+    /// A frontier tile is a covered, unflagged tile next to an uncovered one.
+    pub fn game_jump_frontier(&mut self, forward: bool) {
+        let (w, h) = self.map_size();
+        let (w, h) = (w as usize, h as usize);
+        let total = w * h;
+
+        for _ in 0..self.take_move_count() {
+            let start = self.curr_pos.1 as usize * w + self.curr_pos.0 as usize;
+            let found = (1..total)
+                .map(|i| {
+                    if forward {
+                        (start + i) % total
+                    } else {
+                        (start + total - i) % total
+                    }
+                })
+                .find(|&i| self.is_frontier((i % w) as u16, (i / w) as u16));
+
+            match found {
+                Some(i) => self.curr_pos = ((i % w) as u16, (i / w) as u16),
+                None => break,
             }
         }
+    }
 
-        cnt
+    fn is_open_cover(&self, x: u16, y: u16) -> bool {
+        matches!(
+            self.game.board().tile(x, y).cover,
+            Some(TileCover::Empty) | Some(TileCover::QuestionMark)
+        )
     }
 
+    fn is_frontier(&self, x: u16, y: u16) -> bool {
+        let board = self.game.board();
+        self.is_open_cover(x, y)
+            && board
+                .neighbours(x, y)
+                .any(|(nx, ny)| !board.tile(nx, ny).is_covered())
+    }
+
+    /// Uncovers the tile under the cursor, or in flag mode changes its cover.
+    ///
+    /// Uncovered numbers are chorded either way.
     pub fn uncover_tile(&mut self) {
         let (x, y) = self.curr_pos;
-        let tile = self.mine_map[y as usize][x as usize];
+        if self.flag_mode && self.game.board().tile(x, y).is_covered() {
+            self.act(Action::ChangeCover(x, y));
+        } else {
+            self.act(Action::Uncover(x, y));
+        }
+    }
+
+    pub fn zoom_in(&mut self) {
+        self.zoom = self.zoom.zoom_in();
+    }
+
+    pub fn zoom_out(&mut self) {
+        self.zoom = self.zoom.zoom_out();
+    }
+
+    /// The zoom the board is drawn at: a hex row is offset by half a tile,
+    /// which a one-column tile doesn't have, so hex boards are never compact.
+    pub fn board_zoom(&self) -> Zoom {
+        match self.zoom {
+            Zoom::Compact if self.game.board().grid().shape == Shape::Hex => Zoom::Normal,
+            zoom => zoom,
+        }
+    }
+
+    pub fn toggle_flag_mode(&mut self) {
+        self.flag_mode = !self.flag_mode;
+    }
+
+    /// Moves the cursor to a tile clicked with the mouse.
+    pub fn click_move(&mut self, (x, y): (u16, u16)) {
+        if self.curr_pos != (x, y) {
+            self.curr_pos = (x, y);
+            self.moved();
+        }
+    }
+
+    pub fn change_cover(&mut self) {
+        let (x, y) = self.curr_pos;
+        self.act(Action::ChangeCover(x, y));
+    }
 
-        if let Some(TileCover::FlagMark) = &tile.cover {
+    /// Does a tile action of the player, or asks the co-op host to do it.
+    fn act(&mut self, action: Action) {
+        let (Action::Uncover(x, y) | Action::ChangeCover(x, y) | Action::Move(x, y)) = action;
+        let seen = self.game.board().tile(x, y).cover;
+        match &mut self.coop {
+            Some(coop) if !coop.is_host() => coop.request(action, seen),
+            Some(coop) => {
+                let me = coop.me;
+                self.apply(me, action);
+            }
+            None => self.apply(coop::HOST, action),
+        }
+    }
+
+    /// Applies a tile action of any player to the game.
+    fn apply(&mut self, player: u8, action: Action) {
+        self.record(action);
+        match action {
+            Action::Uncover(x, y) => self.uncover_at(x, y),
+            Action::ChangeCover(x, y) => {
+                let question_marks = self
+                    .setup()
+                    .map_or(self.question_marks, |setup| setup.question_marks);
+                self.game.toggle_flag(x, y, question_marks);
+            }
+            Action::Move(..) => {}
+        }
+
+        if let Some(coop) = &mut self.coop {
+            if coop.is_host() {
+                coop.broadcast(player, action);
+            }
+        }
+    }
+
+    /// Parameters of the board being played or played back, if it has any.
+    fn setup(&self) -> Option<&Replay> {
+        match (&self.recording, &self.playback) {
+            (Some(setup), _) => Some(setup),
+            (None, Some(playback)) => Some(&playback.replay),
+            (None, None) => None,
+        }
+    }
+
+    /// On the first uncover of a board with a safe first click, rebuilds the
+    /// board from its seed if `(x, y)` holds a mine, keeping the tile and its
+    /// neighbours clear. Covers placed so far are kept.
+    fn clear_first_click(&mut self, x: u16, y: u16) {
+        let Some(setup) = self.setup() else {
             return;
-        } else if let None = &tile.cover {
+        };
+        let board = self.game.board();
+        let tile = board.tile(x, y);
+        if !setup.safe_first
+            || setup.no_guess
+            || !tile.is_bomb()
+            || tile.is_flagged()
+            || board.tiles().iter().any(|tile| !tile.is_covered())
+        {
             return;
         }
 
-        match &tile.content {
-            TileContent::Empty(n) => {
-                let cnt = if *n == 0 {
-                    self.uncover_chaining(x, y, true)
-                } else {
-                    self.uncover_chaining(x, y, false)
-                } as u16;
+        let mut rng = StdRng::seed_from_u64(setup.seed);
+        let mut rebuilt = Board::generate_stacked(
+            *board.grid(),
+            setup.bomb_cnt,
+            setup.max_mines,
+            &mut rng,
+            Some((x, y)),
+        );
+        for (new, old) in rebuilt.tiles_mut().iter_mut().zip(board.tiles()) {
+            new.cover = old.cover;
+        }
+        self.game = GameState::new(rebuilt).with_lives(self.game.lives());
+    }
 
-                self.empty_cnt = self.empty_cnt.saturating_sub(cnt);
-                if self.empty_cnt == 0 {
-                    self.over = true;
-                }
+    fn uncover_at(&mut self, x: u16, y: u16) {
+        self.clear_first_click(x, y);
+        let covered = self.game.board().tile(x, y).is_covered();
+        // the tiles this click or chord may uncover itself, to find new openings
+        let board = self.game.board();
+        let targets: Vec<(u16, u16)> = if covered {
+            vec![(x, y)]
+        } else {
+            board
+                .neighbours(x, y)
+                .filter(|&(nx, ny)| board.tile(nx, ny).is_covered())
+                .collect()
+        };
+        let outcome = if covered {
+            self.game.reveal(x, y)
+        } else {
+            self.game.chord(x, y)
+        };
+        self.check_puzzle(outcome);
+        match outcome {
+            Outcome::Revealed(n) if n >= effects::BIG_CASCADE => {
+                self.effects.trigger(Feedback::Cascade)
             }
-            TileContent::Bomb => {
-                self.game_over();
-                return;
+            Outcome::Won => self.effects.trigger(Feedback::Win),
+            Outcome::Lost(..) | Outcome::Hit(..) => self.effects.trigger(Feedback::Loss),
+            _ => {}
+        }
+
+        if let (Some(_), Outcome::Revealed(_)) = (self.time_limit, outcome) {
+            let openings = self.new_openings(&targets);
+            if let Some(limit) = &mut self.time_limit {
+                *limit += mode::OPENING_BONUS * openings;
+            }
+        }
+
+        if let Some(race) = &mut self.race {
+            if let Outcome::Revealed(_) | Outcome::Won = outcome {
+                let board = self.game.board();
+                let total = board.tiles().iter().filter(|t| !t.is_bomb()).count() as u32;
+                race.send_progress(total - self.game.empty_cnt(), total);
+            }
+        }
+
+        if let Outcome::Won | Outcome::Lost(_, _) = outcome {
+            self.finish();
+            let ms = self.elapsed().as_millis() as u64;
+            if let Some(race) = &mut self.race {
+                race.finish(outcome == Outcome::Won, ms);
             }
         }
     }
 
-    pub fn change_cover(&mut self) {
-        let (x, y) = self.curr_pos;
-        let tile = &mut self.mine_map[y as usize][x as usize];
-        match &tile.cover {
-            Some(cover) => {
-                tile.cover = Some(cover.next_cover());
+    /// Openings uncovered from `targets`: zero tiles among them that are
+    /// uncovered now, counting each connected region of zeros once.
+    fn new_openings(&self, targets: &[(u16, u16)]) -> u32 {
+        let board = self.game.board();
+        let is_opening = |(x, y): (u16, u16)| {
+            let tile = board.tile(x, y);
+            !tile.is_covered() && tile.content == TileContent::Empty(0)
+        };
+        let mut seen = HashSet::new();
+        let mut openings = 0;
+        for &start in targets {
+            if !is_opening(start) || !seen.insert(start) {
+                continue;
+            }
+            openings += 1;
+            let mut todo = vec![start];
+            while let Some((x, y)) = todo.pop() {
+                for next in board.neighbours(x, y) {
+                    if is_opening(next) && seen.insert(next) {
+                        todo.push(next);
+                    }
+                }
             }
-            None => {}
         }
+        openings
+    }
+
+    /// Time left of a time attack game.
+    pub fn time_left(&self) -> Option<Duration> {
+        self.time_limit
+            .map(|limit| limit.saturating_sub(self.elapsed()))
+    }
+
+    /// Ends a time attack game whose time ran out.
+    fn check_time(&mut self) {
+        if self.game.is_over() || self.time_left() != Some(Duration::ZERO) {
+            return;
+        }
+        self.game.end();
+        self.effects.trigger(Feedback::Loss);
+        self.finish();
+    }
+
+    /// Who won the race, if this game is one and it is decided.
+    pub fn race_winner(&self) -> Option<Winner> {
+        self.race.as_ref().and_then(|race| race.winner)
     }
 }
@@ -0,0 +1,186 @@
+use std::{
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Result};
+use minesweeper_engine::{Board, Grid, Neighbourhood, Shape};
+
+use crate::storage;
+
+const BOARD_HEADER: &str = "minesweeper-board 1";
+const EXPORTED_FILE: &str = "exported.board";
+const EDITED_FILE: &str = "edited.board";
+
+/// Where the board exported from a game is saved.
+pub fn exported_path() -> Result<PathBuf> {
+    Ok(storage::data_dir()?.join(EXPORTED_FILE))
+}
+
+/// Where the editor opened from the menu saves its board.
+pub fn edited_path() -> Result<PathBuf> {
+    Ok(storage::data_dir()?.join(EDITED_FILE))
+}
+
+/// Writes the mines of a board as text.
+///
+/// ```text
+/// minesweeper-board 1
+/// size 5 3
+/// ..*..
+/// .....
+/// *...2
+/// ```
+///
+/// `.` is a safe tile, `*` a mine and `2`..`9` a tile with that many mines.
+/// `shape hex`, `wrap 1` and `neighbours knight` lines follow the size for
+/// other grids, see [`Neighbourhood::name`]. After `layers 3` the size is the
+/// size of one layer and the rows of the layers follow each other, a blank line
+/// apart.
+pub fn to_text(board: &Board) -> String {
+    let mut text = String::new();
+    let grid = board.grid();
+    let _ = writeln!(text, "{}", BOARD_HEADER);
+    let _ = writeln!(text, "size {} {}", grid.width, grid.layer_height());
+    if grid.shape != Shape::Square {
+        let _ = writeln!(text, "shape {}", grid.shape.name());
+    }
+    if grid.wrap {
+        let _ = writeln!(text, "wrap 1");
+    }
+    if grid.neighbourhood != Neighbourhood::Standard {
+        let _ = writeln!(text, "neighbours {}", grid.neighbourhood.name());
+    }
+    if grid.is_layered() {
+        let _ = writeln!(text, "layers {}", grid.layers);
+    }
+    for y in 0..grid.height {
+        if y > 0 && y % grid.layer_height() == 0 {
+            let _ = writeln!(text);
+        }
+        let row: String = (0..grid.width)
+            .map(|x| match board.tile(x, y).mines() {
+                0 => '.',
+                1 => '*',
+                n => char::from(b'0' + n.min(9)),
+            })
+            .collect();
+        let _ = writeln!(text, "{}", row);
+    }
+    text
+}
+
+/// Reads a board written by [`to_text`] or by hand; numbers are computed from the mines.
+///
+/// Lines starting with `#` are comments.
+pub fn from_text(text: &str) -> Result<Board> {
+    let mut lines = text.lines().enumerate();
+    match lines.next() {
+        Some((_, BOARD_HEADER)) => {}
+        _ => bail!("not a board file (expected `{}`)", BOARD_HEADER),
+    }
+    let (board, _) = parse_body(lines, |_| None)?;
+    Ok(board)
+}
+
+/// Extra tiles of a board body and where they are.
+pub(crate) type Marks = Vec<((u16, u16), char)>;
+
+/// Reads the size, grid and rows of a board from numbered lines.
+///
+/// `extra` gives the tiles besides `.`, `*` and `2`..`9` that may appear in
+/// rows: whether such a tile is a mine. Where they were found is returned with
+/// the board.
+pub(crate) fn parse_body<'a>(
+    lines: impl Iterator<Item = (usize, &'a str)>,
+    extra: impl Fn(char) -> Option<bool>,
+) -> Result<(Board, Marks)> {
+    let mut size = None;
+    let mut shape = Shape::Square;
+    let mut wrap = false;
+    let mut neighbourhood = Neighbourhood::Standard;
+    let mut layers = 1;
+    let mut mines = vec![];
+    let mut marks = vec![];
+    let mut rows = 0;
+    for (i, line) in lines {
+        let line = line.trim();
+        let error = |message: &str| anyhow!("line {}: {}", i + 1, message);
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields[..] {
+            [] => continue,
+            [comment, ..] if comment.starts_with('#') => continue,
+            ["size", w, h] => size = Some((w.parse::<u16>()?, h.parse::<u16>()?)),
+            ["shape", name] => {
+                shape = Shape::from_name(name).ok_or_else(|| error("unknown shape"))?
+            }
+            ["wrap", n] => wrap = n == "1",
+            ["neighbours", name] => {
+                neighbourhood =
+                    Neighbourhood::from_name(name).ok_or_else(|| error("unknown neighbourhood"))?
+            }
+            ["layers", n] => layers = n.parse::<u16>()?.max(1),
+            [row] => {
+                let (width, height) = size.ok_or_else(|| error("rows must follow `size`"))?;
+                if rows == height.saturating_mul(layers) {
+                    return Err(error("more rows than the height"));
+                }
+                if row.chars().count() != width as usize {
+                    return Err(error(&format!("row is not {} tiles wide", width)));
+                }
+                for (x, ch) in row.chars().enumerate() {
+                    let pos = (x as u16, rows);
+                    let n = match ch {
+                        '.' => continue,
+                        '*' => 1,
+                        '2'..='9' => ch as u8 - b'0',
+                        _ => match extra(ch) {
+                            Some(mine) => {
+                                marks.push((pos, ch));
+                                if !mine {
+                                    continue;
+                                }
+                                1
+                            }
+                            None => return Err(error(&format!("unknown tile `{}`", ch))),
+                        },
+                    };
+                    mines.push((pos, n));
+                }
+                rows += 1;
+            }
+            _ => return Err(error(&format!("malformed line `{}`", line))),
+        }
+    }
+
+    let Some((width, height)) = size else {
+        bail!("board has no size");
+    };
+    if width == 0 || height == 0 {
+        bail!("board has no tiles");
+    }
+    if layers > 1 && (shape == Shape::Hex || wrap || neighbourhood != Neighbourhood::Standard) {
+        bail!("layered boards cannot be hex, wrap or use other neighbours");
+    }
+    let height = height.saturating_mul(layers);
+    if rows != height {
+        bail!("board has {} rows, expected {}", rows, height);
+    }
+    if mines.len() == width as usize * height as usize {
+        bail!("board has no safe tile");
+    }
+
+    let max_mines = mines.iter().map(|&(_, n)| n).max().unwrap_or(1);
+    let grid = Grid::new(width, height, shape)
+        .with_wrap(wrap)
+        .with_neighbourhood(neighbourhood)
+        .with_layers(layers);
+    Ok((Board::with_mines(grid, max_mines, mines), marks))
+}
+
+pub fn load(path: &Path) -> Result<Board> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| anyhow!("cannot read board {}: {}", path.display(), e))?;
+    from_text(&text).map_err(|e| anyhow!("{}: {}", path.display(), e))
+}
@@ -0,0 +1,423 @@
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Result};
+use minesweeper_engine::{Neighbourhood, Shape};
+
+use crate::{
+    app::{mines_at_density, GameLevel, MapSize},
+    effects::{Feedback, Sound},
+    mode::GameMode,
+    theme::Theme,
+};
+
+/// Most tiles of a board, every layer counted: 1000x1000.
+pub const MAX_TILES: u64 = 1_000_000;
+
+pub const USAGE: &str = "\
+Usage: minesweeper [OPTIONS]
+
+Starts at the menu unless a board is given with the options below.
+
+Board:
+  -s, --size <SIZE>      map size preset: large (50x40), normal (40x30), small (20x15),
+                         or a classic board with its own level: beginner (9x9,
+                         10 mines), intermediate (16x16, 40), expert (30x16, 99)
+  -l, --level <LEVEL>    mine density: hard (20.6%), normal (15.6%), easy (12.3%)
+  -W, --width <N>        custom board width (needs --height, 1000000 tiles at most)
+  -H, --height <N>       custom board height (needs --width)
+  -d, --density <PCT>    percentage of tiles with a mine, instead of the level
+  -m, --mines <N>        number of mines, instead of the density
+      --shape <SHAPE>    tile shape: square, hex
+      --wrap             opposite edges touch each other (torus), every tile
+                         has a full set of neighbours
+      --stack <N>        up to N mines on one tile (1-9), numbers count every mine
+      --neighbours <N>   which tiles numbers count on square boards: standard,
+                         knight (knight's moves), ring (border of the 5x5 square),
+                         orthogonal, or offsets like `1,2;2,1;-1,2` (each at
+                         most 2 tiles away)
+      --layers <N>       stack N layers of the board size (2-9) into a 3D board,
+                         numbers count the 26 tiles around a tile; < and > (or
+                         PageUp and PageDown) move between layers
+      --board <FILE>     play a board from a file: `.` safe, `*` mine (see README);
+                         E in game exports the board to <data dir>/exported.board
+      --edit <FILE>      edit the board in FILE, or a new one of the board size:
+                         c toggles a mine, t tests it with the solver, s saves it
+                         to FILE, p plays it (e in the menu edits
+                         <data dir>/edited.board)
+
+Game:
+      --seed <N>         generate every board from this seed
+      --theme <THEME>    colour theme: classic, dark, mono, plain (no colours)
+      --sound <SOUND>    sound cues on a big opening, a loss and a win:
+                         off (default), bell, osc (terminal notification)
+      --effects <LIST>   visual effects to show, comma separated: cascade, loss,
+                         win (default: all), or none
+      --accessible       announce the tile under the cursor in a status line for
+                         screen readers, without colours unless --theme is given
+      --no-guess         only generate boards that can be cleared without guessing
+      --mode <MODE>      rules: classic (default), lives (hit mines cost a life),
+                         time-attack (beat the clock, openings add time)
+      --lives <N>        lives in lives mode (default 3), implies --mode lives
+      --puzzles <FILE>   open the puzzle list on this puzzle pack instead of
+                         the built-in one (see README)
+      --replay <FILE>    play back a recorded game (the last game is saved to
+                         <data dir>/last.replay)
+
+Multiplayer (two players race on the same board, first to clear it wins):
+      --host <PORT>      wait for an opponent on this TCP port, then start the
+                         board given by the board options (normal preset by default)
+      --connect <ADDR>   join a game hosted at ADDR, e.g. 192.168.1.20:7878
+      --coop             with --host or --connect: play one shared board together
+                         instead of racing; players can join any time
+
+Spectators (follow a game read-only, without seeing hidden mines):
+      --publish <PORT>   let spectators on this machine follow your games on
+                         this TCP port
+      --public           with --publish: let spectators on other machines follow
+                         too (listens on every network interface)
+      --watch <ADDR>     follow the game published at ADDR: a port on this
+                         machine or HOST:PORT
+
+Other:
+      --stats            print statistics to stdout and exit
+  -h, --help             print this help and exit
+
+The data directory is $MINESWEEPER_DATA_DIR, $XDG_DATA_HOME/minesweeper
+or ~/.local/share/minesweeper. Settings are kept in settings.conf in
+$MINESWEEPER_CONFIG_DIR, $XDG_CONFIG_HOME/minesweeper or ~/.config/minesweeper;
+the options above win over them for one run.
+";
+
+/// Command line options.
+#[derive(Debug, Default)]
+pub struct Cli {
+    pub size: Option<MapSize>,
+    pub level: Option<GameLevel>,
+    pub width: Option<u16>,
+    pub height: Option<u16>,
+    pub mines: Option<u32>,
+    /// share of the tiles with a mine, 0 to 1
+    pub density: Option<f64>,
+    pub shape: Option<Shape>,
+    pub wrap: bool,
+    pub stack: Option<u8>,
+    pub neighbourhood: Option<Neighbourhood>,
+    pub layers: Option<u16>,
+    pub seed: Option<u64>,
+    pub theme: Option<Theme>,
+    pub no_guess: bool,
+    pub mode: Option<GameMode>,
+    pub lives: Option<u32>,
+    pub accessible: bool,
+    pub sound: Option<Sound>,
+    pub effects: Option<Vec<Feedback>>,
+    pub replay: Option<PathBuf>,
+    pub board_path: Option<PathBuf>,
+    /// board file to open in the editor, created on save if missing
+    pub edit: Option<PathBuf>,
+    pub puzzles: Option<PathBuf>,
+    pub host: Option<u16>,
+    pub connect: Option<String>,
+    pub coop: bool,
+    pub publish: Option<u16>,
+    /// publish on every network interface instead of this machine only
+    pub public: bool,
+    /// address of the game to watch, a bare port meaning this machine
+    pub watch: Option<String>,
+    pub stats: bool,
+    pub help: bool,
+}
+
+fn parse_map_size(value: &str) -> Result<MapSize> {
+    MapSize::from_name(value).ok_or_else(|| {
+        anyhow!(
+            "unknown size `{}` (expected large, normal, small, beginner, intermediate or expert)",
+            value
+        )
+    })
+}
+
+fn parse_game_level(value: &str) -> Result<GameLevel> {
+    GameLevel::from_name(value)
+        .ok_or_else(|| anyhow!("unknown level `{}` (expected hard, normal or easy)", value))
+}
+
+fn parse_density(flag: &str, value: &str) -> Result<f64> {
+    let percent: f64 = parse_number(flag, value)?;
+    if !(percent > 0.0 && percent < 100.0) {
+        bail!("{} must be a percentage between 0 and 100", flag);
+    }
+    Ok(percent / 100.0)
+}
+
+fn parse_effects(value: &str) -> Result<Vec<Feedback>> {
+    if value == "none" {
+        return Ok(vec![]);
+    }
+    value
+        .split(',')
+        .map(|name| {
+            Feedback::from_name(name).ok_or_else(|| {
+                anyhow!(
+                    "unknown effect `{}` (expected cascade, loss, win or none)",
+                    name
+                )
+            })
+        })
+        .collect()
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T> {
+    value
+        .parse()
+        .map_err(|_| anyhow!("invalid number `{}` for {}", value, flag))
+}
+
+impl Cli {
+    /// Parses the arguments, without the program name.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut cli = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            // accept both `--flag value` and `--flag=value`
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(value.to_string()))
+                }
+                _ => (arg, None),
+            };
+            let mut value = || {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| anyhow!("{} needs a value", flag))
+            };
+
+            match flag.as_str() {
+                "-s" | "--size" => cli.size = Some(parse_map_size(&value()?)?),
+                "-l" | "--level" => cli.level = Some(parse_game_level(&value()?)?),
+                "-W" | "--width" => cli.width = Some(parse_number(&flag, &value()?)?),
+                "-H" | "--height" => cli.height = Some(parse_number(&flag, &value()?)?),
+                "-m" | "--mines" => cli.mines = Some(parse_number(&flag, &value()?)?),
+                "-d" | "--density" => cli.density = Some(parse_density(&flag, &value()?)?),
+                "--shape" => {
+                    let name = value()?;
+                    let shape = Shape::from_name(&name).ok_or_else(|| {
+                        anyhow!("unknown shape `{}` (expected square or hex)", name)
+                    })?;
+                    cli.shape = Some(shape);
+                }
+                "--wrap" => cli.wrap = true,
+                "--stack" => cli.stack = Some(parse_number(&flag, &value()?)?),
+                "--neighbours" => {
+                    let name = value()?;
+                    let neighbourhood = Neighbourhood::from_name(&name).ok_or_else(|| {
+                        anyhow!(
+                            "unknown neighbourhood `{}` (expected standard, knight, ring, \
+                             orthogonal or offsets like 1,2;2,1)",
+                            name
+                        )
+                    })?;
+                    cli.neighbourhood = Some(neighbourhood);
+                }
+                "--layers" => cli.layers = Some(parse_number(&flag, &value()?)?),
+                "--seed" => cli.seed = Some(parse_number(&flag, &value()?)?),
+                "--theme" => {
+                    let name = value()?;
+                    let theme = Theme::from_name(&name).ok_or_else(|| {
+                        anyhow!(
+                            "unknown theme `{}` (expected classic, dark, mono or plain)",
+                            name
+                        )
+                    })?;
+                    cli.theme = Some(theme);
+                }
+                "--no-guess" => cli.no_guess = true,
+                "--mode" => {
+                    let name = value()?;
+                    let mode = GameMode::from_name(&name).ok_or_else(|| {
+                        anyhow!(
+                            "unknown mode `{}` (expected classic, lives or time-attack)",
+                            name
+                        )
+                    })?;
+                    cli.mode = Some(mode);
+                }
+                "--lives" => {
+                    let lives: u32 = parse_number(&flag, &value()?)?;
+                    if lives == 0 {
+                        bail!("--lives must be at least 1");
+                    }
+                    cli.lives = Some(lives);
+                }
+                "--accessible" => cli.accessible = true,
+                "--sound" => {
+                    let name = value()?;
+                    let sound = Sound::from_name(&name).ok_or_else(|| {
+                        anyhow!("unknown sound `{}` (expected off, bell or osc)", name)
+                    })?;
+                    cli.sound = Some(sound);
+                }
+                "--effects" => cli.effects = Some(parse_effects(&value()?)?),
+                "--replay" => cli.replay = Some(PathBuf::from(value()?)),
+                "--puzzles" => cli.puzzles = Some(PathBuf::from(value()?)),
+                "--board" => cli.board_path = Some(PathBuf::from(value()?)),
+                "--edit" => cli.edit = Some(PathBuf::from(value()?)),
+                "--host" => cli.host = Some(parse_number(&flag, &value()?)?),
+                "--connect" => cli.connect = Some(value()?),
+                "--coop" => cli.coop = true,
+                "--publish" => cli.publish = Some(parse_number(&flag, &value()?)?),
+                "--public" => cli.public = true,
+                "--watch" => {
+                    let addr = value()?;
+                    cli.watch = Some(match addr.parse::<u16>() {
+                        Ok(port) => format!("127.0.0.1:{}", port),
+                        Err(_) => addr,
+                    });
+                }
+                "--stats" => cli.stats = true,
+                "-h" | "--help" => cli.help = true,
+                _ => bail!("unknown option `{}`", flag),
+            }
+        }
+
+        // --lives on its own picks lives mode
+        match cli.mode {
+            _ if cli.lives.is_none() => {}
+            None => cli.mode = Some(GameMode::Lives),
+            Some(GameMode::Lives) => {}
+            Some(mode) => bail!("--lives cannot be used with --mode {}", mode.name()),
+        }
+
+        Ok(cli)
+    }
+
+    /// The board to start right away, if any board option was given.
+    ///
+    /// Returns `((width, height), mines)`. Options that don't go together are
+    /// an error even without a board.
+    pub fn board(&self) -> Result<Option<((u16, u16), u32)>> {
+        let starts = [
+            ("--replay", self.replay.is_some()),
+            ("--board", self.board_path.is_some()),
+            ("--edit", self.edit.is_some()),
+            ("--puzzles", self.puzzles.is_some()),
+            ("--host", self.host.is_some()),
+            ("--connect", self.connect.is_some()),
+            ("--watch", self.watch.is_some()),
+        ];
+        let mut given = starts
+            .iter()
+            .filter(|(_, given)| *given)
+            .map(|(flag, _)| *flag);
+        let start = given.next();
+        if let Some(other) = given.next() {
+            bail!(
+                "{} and {} cannot be used together",
+                start.unwrap_or_default(),
+                other
+            );
+        }
+        if self.coop && !matches!(start, Some("--host" | "--connect")) {
+            bail!("--coop needs --host or --connect");
+        }
+        if self.publish.is_some() && start == Some("--watch") {
+            bail!("--publish and --watch cannot be used together");
+        }
+        if self.public && self.publish.is_none() {
+            bail!("--public needs --publish");
+        }
+
+        let sized = self.size.is_some()
+            || self.level.is_some()
+            || self.width.is_some()
+            || self.height.is_some()
+            || self.mines.is_some()
+            || self.density.is_some();
+        let shaped = sized
+            || self.shape.is_some()
+            || self.wrap
+            || self.stack.is_some()
+            || self.neighbourhood.is_some()
+            || self.layers.is_some()
+            || self.no_guess;
+        // these play a board made elsewhere
+        if let Some(flag @ ("--replay" | "--board" | "--puzzles" | "--connect" | "--watch")) = start
+        {
+            if shaped {
+                bail!(
+                    "{} brings its own board, board options cannot be used with it",
+                    flag
+                );
+            }
+        }
+
+        let stack = self.stack.unwrap_or(1);
+        if !(1..=9).contains(&stack) {
+            bail!("--stack must be between 1 and 9");
+        }
+        if stack > 1 && self.no_guess {
+            bail!("--no-guess does not work with --stack");
+        }
+        if self.neighbourhood.is_some() && self.shape == Some(Shape::Hex) {
+            bail!("--neighbours only works on square boards");
+        }
+        let layers = self.layers.unwrap_or(1);
+        if !(1..=9).contains(&layers) {
+            bail!("--layers must be between 1 and 9");
+        }
+        if layers > 1
+            && (self.shape == Some(Shape::Hex) || self.wrap || self.neighbourhood.is_some())
+        {
+            bail!("--layers does not work with --shape hex, --wrap or --neighbours");
+        }
+
+        // the menu has sizes of odd height
+        if !sized && start.is_none() && self.wrap && self.shape == Some(Shape::Hex) {
+            bail!("a wrapping hex board needs a board size with an even height");
+        }
+        if !sized {
+            return Ok(None);
+        }
+
+        let size = self.size.unwrap_or(MapSize::Normal);
+        // a classic size brings its level, unless another one is asked for
+        let level = self.level.or(size.level()).unwrap_or(GameLevel::Normal);
+
+        let (width, height) = match (self.width, self.height) {
+            (Some(w), Some(h)) => (w, h),
+            (None, None) => size.map_size(),
+            _ => bail!("--width and --height must be given together"),
+        };
+        if width < 2 || height < 2 {
+            bail!("the board must be at least 2x2");
+        }
+        if height.checked_mul(layers).is_none() {
+            bail!("the board is too tall for {} layers", layers);
+        }
+
+        // the density counts the tiles of every layer
+        let tiles = width as u64 * height as u64 * layers as u64;
+        if tiles > MAX_TILES {
+            bail!("the board must have at most {} tiles", MAX_TILES);
+        }
+        let mines = match (self.mines, self.density) {
+            (Some(_), Some(_)) => bail!("--mines and --density cannot be used together"),
+            (Some(mines), None) => mines,
+            (None, Some(density)) => mines_at_density(tiles, density),
+            (None, None) => mines_at_density(tiles, level.density()),
+        };
+
+        if self.wrap && self.shape == Some(Shape::Hex) && height % 2 == 1 {
+            bail!("a wrapping hex board needs an even height");
+        }
+        // at least one tile has to stay safe
+        let max = (tiles - 1) * stack as u64;
+        if mines == 0 || mines as u64 > max {
+            bail!("--mines must be between 1 and {}", max);
+        }
+
+        Ok(Some(((width, height), mines)))
+    }
+}
@@ -0,0 +1,251 @@
+use std::{collections::BTreeMap, net::TcpListener, thread};
+
+use anyhow::{bail, Result};
+use crossbeam::channel::{self, Receiver};
+use minesweeper_engine::TileCover;
+
+use crate::{
+    net::{Message, Mode, Peer},
+    replay::{Action, Replay},
+};
+
+/// Player number of the host.
+pub const HOST: u8 = 0;
+
+/// Something the [`App`](crate::app::App) has to act on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoopEvent {
+    /// Host only: a guest wants to do something, having seen the tile with this cover.
+    Request {
+        player: u8,
+        action: Action,
+        seen: Option<TileCover>,
+    },
+    /// Guest only: the host did this, apply it as is.
+    Apply { player: u8, action: Action },
+}
+
+#[derive(Debug)]
+enum Role {
+    Host {
+        /// guests that connected since the last poll
+        joining: Receiver<Peer>,
+        guests: Vec<(u8, Peer)>,
+        /// `None` once every player number was handed out
+        next_player: Option<u8>,
+        setup: Replay,
+        /// every tile action applied so far, for players joining late
+        log: Vec<(u8, Action)>,
+    },
+    Guest {
+        peer: Peer,
+    },
+}
+
+/// A board shared by several players.
+///
+/// The host keeps the real game: guests send it [`Message::Act`] requests and
+/// only change their board when the host sends back what it
+/// [applied](Message::Applied). The host handles requests one at a time, so
+/// when two players act on the same tile at once the first one wins and the
+/// other request, made on a tile that has changed since, is dropped.
+#[derive(Debug)]
+pub struct Coop {
+    role: Role,
+    /// our player number
+    pub me: u8,
+    /// cursor of every player, ours included
+    pub cursors: BTreeMap<u8, (u16, u16)>,
+    /// guest only: the host quit or the connection broke
+    pub host_left: bool,
+}
+
+impl Coop {
+    /// Hosts the board of `setup`; guests can join on `listener` at any time.
+    pub fn host(listener: TcpListener, setup: Replay) -> Self {
+        let (sender, joining) = channel::unbounded();
+        thread::spawn(move || {
+            while let Ok(peer) = Peer::accept(&listener, Mode::Coop) {
+                if sender.send(peer).is_err() {
+                    return;
+                }
+            }
+        });
+
+        Self {
+            role: Role::Host {
+                joining,
+                guests: vec![],
+                next_player: Some(HOST + 1),
+                setup,
+                log: vec![],
+            },
+            me: HOST,
+            cursors: BTreeMap::new(),
+            host_left: false,
+        }
+    }
+
+    /// Joins a shared board, returning the board parameters sent by the host.
+    pub fn join(peer: Peer) -> Result<(Self, Replay)> {
+        let mut me = None;
+        loop {
+            match peer.recv()? {
+                Message::Welcome { player } => me = Some(player),
+                Message::Board(setup) => {
+                    let Some(me) = me else {
+                        bail!("the host sent a board before a player number");
+                    };
+                    let coop = Self {
+                        role: Role::Guest { peer },
+                        me,
+                        cursors: BTreeMap::new(),
+                        host_left: false,
+                    };
+                    return Ok((coop, setup));
+                }
+                Message::Bye => bail!("the host left without sending a playable board"),
+                _ => {}
+            }
+        }
+    }
+
+    /// Guest only: leaves a host that sent something impossible.
+    pub fn hang_up(&mut self) {
+        if let Role::Guest { peer } = &mut self.role {
+            peer.close();
+        }
+        self.host_left = true;
+    }
+
+    pub fn is_host(&self) -> bool {
+        matches!(self.role, Role::Host { .. })
+    }
+
+    /// Number of players on the board, us included.
+    pub fn players(&self) -> usize {
+        match &self.role {
+            Role::Host { guests, .. } => guests.len() + 1,
+            Role::Guest { .. } => self.cursors.len().max(1),
+        }
+    }
+
+    /// Guest only: asks the host to do `action`.
+    pub fn request(&mut self, action: Action, seen: Option<TileCover>) {
+        if let Action::Move(x, y) = action {
+            self.cursors.insert(self.me, (x, y));
+        }
+        if let Role::Guest { peer } = &mut self.role {
+            let _ = peer.send(&Message::Act { action, seen });
+        }
+    }
+
+    /// Host only: tells every guest that `action` was applied for `player`.
+    pub fn broadcast(&mut self, player: u8, action: Action) {
+        if let Action::Move(x, y) = action {
+            self.cursors.insert(player, (x, y));
+        }
+        if let Role::Host { guests, log, .. } = &mut self.role {
+            if !matches!(action, Action::Move(..)) {
+                log.push((player, action));
+            }
+            for (_, peer) in guests.iter_mut() {
+                let _ = peer.send(&Message::Applied { player, action });
+            }
+        }
+    }
+
+    /// Handles what arrived over the network since the last poll.
+    pub fn poll(&mut self) -> Vec<CoopEvent> {
+        let mut events = vec![];
+        match &mut self.role {
+            Role::Host {
+                joining,
+                guests,
+                next_player,
+                setup,
+                log,
+            } => {
+                // catch new players up: their number, the board, what happened so far
+                while let Ok(mut peer) = joining.try_recv() {
+                    // once the player numbers run out, joiners are hung up on
+                    let Some(player) = *next_player else {
+                        continue;
+                    };
+                    *next_player = player.checked_add(1);
+                    let _ = peer.send(&Message::Welcome { player });
+                    let _ = peer.send(&Message::Board(setup.clone()));
+                    for &(player, action) in log.iter() {
+                        let _ = peer.send(&Message::Applied { player, action });
+                    }
+                    for (&other, &(x, y)) in &self.cursors {
+                        let action = Action::Move(x, y);
+                        let _ = peer.send(&Message::Applied {
+                            player: other,
+                            action,
+                        });
+                    }
+                    guests.push((player, peer));
+                }
+
+                let mut left = vec![];
+                for (player, peer) in guests.iter() {
+                    while let Some(message) = peer.try_recv() {
+                        match message {
+                            Message::Act { action, seen } => events.push(CoopEvent::Request {
+                                player: *player,
+                                action,
+                                seen,
+                            }),
+                            Message::Bye => left.push(*player),
+                            _ => {}
+                        }
+                    }
+                }
+                for player in left {
+                    guests.retain(|(other, _)| *other != player);
+                    self.cursors.remove(&player);
+                    for (_, peer) in guests.iter_mut() {
+                        let _ = peer.send(&Message::Left { player });
+                    }
+                }
+            }
+            Role::Guest { peer } => {
+                while let Some(message) = peer.try_recv() {
+                    match message {
+                        Message::Applied {
+                            player,
+                            action: Action::Move(x, y),
+                        } => {
+                            self.cursors.insert(player, (x, y));
+                        }
+                        Message::Applied { player, action } => {
+                            events.push(CoopEvent::Apply { player, action })
+                        }
+                        Message::Left { player } => {
+                            self.cursors.remove(&player);
+                        }
+                        Message::Bye => self.host_left = true,
+                        _ => {}
+                    }
+                }
+            }
+        }
+        events
+    }
+}
+
+impl Drop for Coop {
+    fn drop(&mut self) {
+        match &mut self.role {
+            Role::Host { guests, .. } => {
+                for (_, peer) in guests.iter_mut() {
+                    let _ = peer.send(&Message::Bye);
+                }
+            }
+            Role::Guest { peer } => {
+                let _ = peer.send(&Message::Bye);
+            }
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use std::{collections::BTreeMap, fs, io::ErrorKind, path::PathBuf, time::Duration};
+
+use anyhow::{anyhow, Result};
+use chrono::{Datelike, Local, Months, NaiveDate};
+
+use crate::{
+    app::MapSize,
+    stats::{GameResult, Record},
+    storage,
+};
+
+const DAILY_FILE: &str = "daily.tsv";
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Every daily board has this size and mine count, whatever the player picked in
+/// the menu. The count is fixed rather than a level's density, so the board of a
+/// date never changes.
+pub const DAILY_SIZE: MapSize = MapSize::Small;
+pub const DAILY_MINES: u32 = 35;
+
+/// The local calendar date.
+pub fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+/// Board seed of a date, the same on every machine.
+pub fn seed(date: NaiveDate) -> u64 {
+    // splitmix64 of the date written as a number like 20261018
+    let mut z = (date.year() as u64 * 10000 + date.month() as u64 * 100 + date.day() as u64)
+        .wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Results of the daily boards, one record per date.
+#[derive(Debug, Default)]
+pub struct DailyLog {
+    pub days: BTreeMap<NaiveDate, Record>,
+}
+
+fn daily_path() -> Result<PathBuf> {
+    Ok(storage::data_dir()?.join(DAILY_FILE))
+}
+
+impl DailyLog {
+    /// Loads the daily results, or an empty log if there is none yet.
+    pub fn load() -> Result<Self> {
+        let text = match fs::read_to_string(daily_path()?) {
+            Ok(text) => text,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut days = BTreeMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let fields: Vec<&str> = line.split('\t').collect();
+            let [date, played, won, best_ms] = fields[..] else {
+                return Err(anyhow!("{}:{}: malformed daily line", DAILY_FILE, i + 1));
+            };
+            let best_ms: u64 = best_ms.parse()?;
+            let record = Record {
+                played: played.parse()?,
+                won: won.parse()?,
+                best: (best_ms > 0).then(|| Duration::from_millis(best_ms)),
+            };
+            days.insert(NaiveDate::parse_from_str(date, DATE_FORMAT)?, record);
+        }
+
+        Ok(Self { days })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let text: String = self
+            .days
+            .iter()
+            .map(|(date, r)| {
+                let best_ms = r.best.map_or(0, |best| best.as_millis());
+                let date = date.format(DATE_FORMAT);
+                format!("{}\t{}\t{}\t{}\n", date, r.played, r.won, best_ms)
+            })
+            .collect();
+        fs::write(daily_path()?, text)?;
+        Ok(())
+    }
+
+    pub fn add(&mut self, date: NaiveDate, result: &GameResult) {
+        self.days.entry(date).or_default().add(result);
+    }
+
+    /// Was the board of this date ever cleared?
+    pub fn completed(&self, date: NaiveDate) -> bool {
+        self.days.get(&date).is_some_and(|r| r.won > 0)
+    }
+
+    /// Number of days in a row with a cleared board, up to today.
+    ///
+    /// A streak is not broken before today's board is played.
+    pub fn streak(&self, today: NaiveDate) -> u32 {
+        let mut date = if self.completed(today) {
+            today
+        } else {
+            match today.pred_opt() {
+                Some(date) => date,
+                None => return 0,
+            }
+        };
+
+        let mut streak = 0;
+        while self.completed(date) {
+            streak += 1;
+            match date.pred_opt() {
+                Some(prev) => date = prev,
+                None => break,
+            }
+        }
+        streak
+    }
+}
+
+/// The calendar screen: which month is shown.
+#[derive(Debug, Clone, Copy)]
+pub struct Calendar {
+    pub today: NaiveDate,
+    /// first day of the shown month
+    pub month: NaiveDate,
+}
+
+impl Calendar {
+    pub fn new(today: NaiveDate) -> Self {
+        Self {
+            today,
+            month: today.with_day(1).unwrap_or(today),
+        }
+    }
+
+    pub fn prev_month(&mut self) {
+        if let Some(month) = self.month.checked_sub_months(Months::new(1)) {
+            self.month = month;
+        }
+    }
+
+    /// Shows the next month, but never one after today's.
+    pub fn next_month(&mut self) {
+        if let Some(month) = self.month.checked_add_months(Months::new(1)) {
+            if month <= self.today {
+                self.month = month;
+            }
+        }
+    }
+
+    /// Days of the shown month.
+    pub fn days(&self) -> impl Iterator<Item = NaiveDate> {
+        let month = self.month.month();
+        self.month
+            .iter_days()
+            .take_while(move |date| date.month() == month)
+    }
+}
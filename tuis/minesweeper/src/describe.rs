@@ -0,0 +1,91 @@
+use minesweeper_engine::{Board, Status, Tile, TileContent, TileCover};
+
+use crate::app::App;
+
+const NUMBER_WORDS: [&str; 13] = [
+    "no", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven",
+    "twelve",
+];
+
+/// `n` things in words, like "two flagged neighbours".
+fn count(n: usize, singular: &str, plural: &str) -> String {
+    let noun = if n == 1 { singular } else { plural };
+    match NUMBER_WORDS.get(n) {
+        Some(word) => format!("{} {}", word, noun),
+        None => format!("{} {}", n, noun),
+    }
+}
+
+/// What the player sees of a tile, in words.
+pub fn tile_state(tile: &Tile) -> String {
+    match tile.cover {
+        Some(TileCover::Empty) => "covered".to_string(),
+        Some(TileCover::QuestionMark) => "question mark".to_string(),
+        Some(TileCover::FlagMark(1)) => "flagged".to_string(),
+        Some(TileCover::FlagMark(n)) => format!("flagged {} times", n),
+        None => match tile.content {
+            TileContent::Empty(0) => "empty".to_string(),
+            TileContent::Empty(n) => n.to_string(),
+            TileContent::Bomb(1) => "mine".to_string(),
+            TileContent::Bomb(n) => format!("{} mines", n),
+        },
+    }
+}
+
+/// `(x, y)` the way it is announced, counting rows and columns from 1.
+fn position(x: u16, y: u16) -> String {
+    format!("row {} col {}", y + 1, x + 1)
+}
+
+/// The tile under the cursor, like "row 5 col 7: 3, two flagged neighbours".
+///
+/// Uncovered numbers also tell how many neighbours are flagged, to know when
+/// a chord is safe.
+pub fn status_line(app: &App) -> String {
+    let board = app.game.board();
+    let (x, y) = app.curr_pos;
+    let tile = board.tile(x, y);
+
+    let mut line = match app.game.status() {
+        Status::Won => "won. ".to_string(),
+        Status::Lost => "game over. ".to_string(),
+        Status::Playing => String::new(),
+    };
+    line.push_str(&format!("{}: {}", position(x, y), tile_state(tile)));
+    if let (None, TileContent::Empty(1..)) = (tile.cover, tile.content) {
+        let flagged = board
+            .neighbours(x, y)
+            .filter(|&(nx, ny)| board.tile(nx, ny).is_flagged())
+            .count();
+        line.push_str(&format!(
+            ", {}",
+            count(flagged, "flagged neighbour", "flagged neighbours")
+        ));
+    }
+    line
+}
+
+/// The tile at `(x, y)` and its neighbours, one line per row from top to bottom.
+///
+/// On a square board this is the 3x3 around the tile.
+pub fn surroundings(board: &Board, x: u16, y: u16) -> Vec<String> {
+    let mut tiles: Vec<(u16, u16)> = board.neighbours(x, y).collect();
+    tiles.push((x, y));
+    tiles.sort_by_key(|&(x, y)| (y, x));
+
+    let mut lines: Vec<String> = vec![];
+    let mut row = None;
+    for (tx, ty) in tiles {
+        let state = tile_state(board.tile(tx, ty));
+        let here = if (tx, ty) == (x, y) { " (cursor)" } else { "" };
+        if row == Some(ty) {
+            if let Some(line) = lines.last_mut() {
+                line.push_str(&format!(", col {} {}{}", tx + 1, state, here));
+            }
+        } else {
+            lines.push(format!("row {}: col {} {}{}", ty + 1, tx + 1, state, here));
+            row = Some(ty);
+        }
+    }
+    lines
+}
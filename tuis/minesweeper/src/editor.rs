@@ -0,0 +1,76 @@
+use std::path::PathBuf;
+
+use minesweeper_engine::Board;
+
+/// What the solver said about the edited board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Check {
+    /// it can be cleared without guessing, starting at this tile
+    Solvable((u16, u16)),
+    /// a guess is needed wherever the game starts
+    NeedsGuessing,
+}
+
+/// A board being edited: the mines are placed by hand and every tile is
+/// uncovered, so the numbers show while editing.
+#[derive(Debug, Default)]
+pub struct Editor {
+    /// file the board is saved to, `<data dir>/edited.board` if `None`
+    pub path: Option<PathBuf>,
+    /// result of the last solver check, until the board changes
+    pub check: Option<Check>,
+    /// the board was saved since its last change
+    pub saved: bool,
+    /// text of the board, until someone writes it out
+    pub save: Option<String>,
+}
+
+impl Editor {
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self {
+            path,
+            ..Self::default()
+        }
+    }
+
+    /// The result of the last check or save, or the keys to get one.
+    pub fn status(&self) -> String {
+        match self.check {
+            Some(Check::Solvable((x, y))) => format!("no guessing from {},{}", x, y),
+            Some(Check::NeedsGuessing) => "needs guessing".to_string(),
+            None if self.saved => "saved".to_string(),
+            None => "t: test  s: save  p: play".to_string(),
+        }
+    }
+}
+
+/// `board` with every tile uncovered.
+pub fn uncovered(mut board: Board) -> Board {
+    for tile in board.tiles_mut() {
+        tile.cover = None;
+    }
+    board
+}
+
+/// `board` with a mine added to or taken off `(x, y)`, numbers counted again.
+///
+/// The last safe tile never gets a mine.
+pub fn toggle_mine(board: &Board, x: u16, y: u16) -> Board {
+    let toggled = board.index(x, y);
+    let safe_cnt = board.tiles().iter().filter(|tile| !tile.is_bomb()).count();
+    if !board.tiles()[toggled].is_bomb() && safe_cnt == 1 {
+        return board.clone();
+    }
+
+    let mines = board.tiles().iter().enumerate().filter_map(|(idx, tile)| {
+        let mines = if idx != toggled {
+            tile.mines()
+        } else if tile.is_bomb() {
+            0
+        } else {
+            1
+        };
+        (mines > 0).then(|| (board.position(idx), mines))
+    });
+    uncovered(Board::with_mines(*board.grid(), board.max_mines(), mines))
+}
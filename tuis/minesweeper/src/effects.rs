@@ -0,0 +1,162 @@
+use std::io::{self, Write};
+
+/// Reveals opening at least this many tiles flash the board.
+pub const BIG_CASCADE: u32 = 20;
+
+/// How many ticks of the event loop each visual effect lasts.
+const CASCADE_TICKS: u16 = 4;
+const LOSS_TICKS: u16 = 6;
+const WIN_TICKS: u16 = 30;
+
+/// Something worth some feedback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feedback {
+    /// A reveal opened at least [`BIG_CASCADE`] tiles.
+    Cascade,
+    Loss,
+    Win,
+}
+
+impl Feedback {
+    pub const ALL: [Feedback; 3] = [Feedback::Cascade, Feedback::Loss, Feedback::Win];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Feedback::Cascade => "cascade",
+            Feedback::Loss => "loss",
+            Feedback::Win => "win",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Feedback> {
+        Self::ALL
+            .into_iter()
+            .find(|feedback| feedback.name() == name)
+    }
+
+    fn ticks(&self) -> u16 {
+        match self {
+            Feedback::Cascade => CASCADE_TICKS,
+            Feedback::Loss => LOSS_TICKS,
+            Feedback::Win => WIN_TICKS,
+        }
+    }
+}
+
+/// How sound cues reach the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Sound {
+    #[default]
+    Off,
+    /// The terminal bell.
+    Bell,
+    /// An OSC 9 notification, which terminals like iTerm2 or Windows Terminal
+    /// show (and sound) even when the window is in the background.
+    Osc,
+}
+
+impl Sound {
+    pub const ALL: [Sound; 3] = [Sound::Off, Sound::Bell, Sound::Osc];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Sound::Off => "off",
+            Sound::Bell => "bell",
+            Sound::Osc => "osc",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Sound> {
+        Self::ALL.into_iter().find(|sound| sound.name() == name)
+    }
+}
+
+/// Plays the sound cue of `feedback` on the terminal.
+pub fn play(sound: Sound, feedback: Feedback, out: &mut impl Write) -> io::Result<()> {
+    match sound {
+        Sound::Off => return Ok(()),
+        Sound::Bell => write!(out, "\x07")?,
+        Sound::Osc => {
+            let message = match feedback {
+                Feedback::Cascade => "big opening",
+                Feedback::Loss => "boom, game over",
+                Feedback::Win => "board cleared",
+            };
+            write!(out, "\x1b]9;minesweeper: {}\x07", message)?
+        }
+    }
+    out.flush()
+}
+
+/// Which feedback is turned on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EffectSettings {
+    pub sound: Sound,
+    /// flash the board on a big cascade
+    pub cascade_flash: bool,
+    /// flash the board red on a loss
+    pub loss_flash: bool,
+    /// colour wave over the board on a win
+    pub win_animation: bool,
+}
+
+impl Default for EffectSettings {
+    fn default() -> Self {
+        Self {
+            sound: Sound::Off,
+            cascade_flash: true,
+            loss_flash: true,
+            win_animation: true,
+        }
+    }
+}
+
+impl EffectSettings {
+    pub fn visual(&self, feedback: Feedback) -> bool {
+        match feedback {
+            Feedback::Cascade => self.cascade_flash,
+            Feedback::Loss => self.loss_flash,
+            Feedback::Win => self.win_animation,
+        }
+    }
+
+    pub fn set_visual(&mut self, feedback: Feedback, on: bool) {
+        match feedback {
+            Feedback::Cascade => self.cascade_flash = on,
+            Feedback::Loss => self.loss_flash = on,
+            Feedback::Win => self.win_animation = on,
+        }
+    }
+}
+
+/// Feedback of the game: the visual effect being shown and the sound to play.
+#[derive(Debug, Default)]
+pub struct Effects {
+    pub settings: EffectSettings,
+    /// the visual effect being shown and how many ticks it has run
+    pub active: Option<(Feedback, u16)>,
+    /// sound cue waiting to be played, until someone takes it
+    pub sound: Option<Feedback>,
+}
+
+impl Effects {
+    /// Starts the feedback of an event, as far as it is turned on.
+    pub fn trigger(&mut self, feedback: Feedback) {
+        if self.settings.visual(feedback) {
+            self.active = Some((feedback, 0));
+        }
+        if self.settings.sound != Sound::Off {
+            self.sound = Some(feedback);
+        }
+    }
+
+    /// Advances the visual effect, ending it when its time is up.
+    pub fn tick(&mut self) {
+        if let Some((feedback, ticks)) = &mut self.active {
+            *ticks += 1;
+            if *ticks >= feedback.ticks() {
+                self.active = None;
+            }
+        }
+    }
+}
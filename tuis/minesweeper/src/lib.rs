@@ -0,0 +1,67 @@
+//! Terminal minesweeper built on [`minesweeper_engine`].
+
+/// Application.
+pub mod app;
+
+/// Hand-made boards in a text format.
+pub mod board_file;
+
+/// Command line options.
+pub mod cli;
+
+/// Board shared by several players over the network.
+pub mod coop;
+
+/// Daily challenge boards.
+pub mod daily;
+
+/// Text descriptions of the board for screen readers.
+pub mod describe;
+
+/// Board editor.
+pub mod editor;
+
+/// Sound cues and visual effects on game events.
+pub mod effects;
+
+/// Terminal events handler.
+pub mod event;
+
+/// Lives and time attack game modes.
+pub mod mode;
+
+/// Network connection to another instance.
+pub mod net;
+
+/// Puzzle packs and puzzle progress.
+pub mod puzzle;
+
+/// Head-to-head race against another instance.
+pub mod race;
+
+/// Game recording and playback.
+pub mod replay;
+
+/// Preferences kept in the config file.
+pub mod settings;
+
+/// Game statistics.
+pub mod stats;
+
+/// Persistent data and configuration locations.
+pub mod storage;
+
+/// Tile colours and symbols.
+pub mod theme;
+
+/// Widget renderer.
+pub mod ui;
+
+/// Terminal user interface.
+pub mod tui;
+
+/// Application updater.
+pub mod update;
+
+/// Spectators following a game read-only.
+pub mod watch;
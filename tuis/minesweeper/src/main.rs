@@ -1,28 +1,136 @@
-/// Application.
-pub mod app;
+use std::{net::TcpListener, path::PathBuf};
 
-/// Terminal events handler.
-pub mod event;
+use anyhow::{anyhow, Result};
+use minesweeper::{
+    app::{App, GameLevel, MapSize},
+    board_file,
+    cli::{self, Cli},
+    daily::DailyLog,
+    effects::{self, Feedback},
+    event::{Event, EventHandler},
+    net::{Mode, Peer},
+    puzzle::{PuzzlePack, PuzzleProgress},
+    replay::{self, Replay},
+    settings::Settings,
+    stats::Stats,
+    theme::Theme,
+    tui::Tui,
+    update::{update, update_mouse},
+};
+use ratatui::{backend::CrosstermBackend, Terminal};
 
-/// Widget renderer.
-pub mod ui;
+fn main() -> Result<()> {
+    let cli = Cli::parse(std::env::args().skip(1))
+        .map_err(|e| anyhow!("{}\nTry `minesweeper --help` for more information.", e))?;
 
-/// Terminal user interface.
-pub mod tui;
+    if cli.help {
+        print!("{}", cli::USAGE);
+        return Ok(());
+    }
 
-/// Application updater.
-pub mod update;
+    if cli.stats {
+        print!("{}", Stats::load()?.table());
+        return Ok(());
+    }
 
-use anyhow::Result;
-use app::App;
-use event::{Event, EventHandler};
-use ratatui::{backend::CrosstermBackend, Terminal};
-use tui::Tui;
-use update::update;
+    let board = cli.board()?;
 
-fn main() -> Result<()> {
     // Create an application.
     let mut app = App::new();
+    let (settings, warnings) = Settings::load();
+    app.settings = settings;
+    app.apply_settings();
+    app.warning = match &warnings[..] {
+        [] => None,
+        [warning] => Some(warning.clone()),
+        [warning, rest @ ..] => Some(format!("{} (and {} more)", warning, rest.len())),
+    };
+
+    // command line options win over the settings for this run
+    app.accessible = cli.accessible;
+    if let Some(sound) = cli.sound {
+        app.effects.settings.sound = sound;
+    }
+    if let Some(enabled) = &cli.effects {
+        for feedback in Feedback::ALL {
+            app.effects
+                .settings
+                .set_visual(feedback, enabled.contains(&feedback));
+        }
+    }
+    match cli.theme {
+        Some(theme) => app.theme = theme,
+        None if cli.accessible => app.theme = Theme::Plain,
+        None => {}
+    }
+    app.mode = cli.mode.unwrap_or_default();
+    if let Some(lives) = cli.lives {
+        app.lives = lives;
+    }
+    app.seed = cli.seed;
+    app.no_guess = cli.no_guess;
+    app.shape = cli.shape.unwrap_or_default();
+    app.wrap = cli.wrap;
+    app.neighbourhood = cli.neighbourhood.unwrap_or_default();
+    app.layers = cli.layers.unwrap_or(1);
+    app.max_mines = cli.stack.unwrap_or(1);
+    app.daily_log = DailyLog::load()?;
+    app.puzzle_progress = PuzzleProgress::load()?;
+
+    if let Some(path) = &cli.replay {
+        app.start_playback(Replay::load(path)?);
+    } else if let Some(path) = &cli.puzzles {
+        app.puzzle_pack = PuzzlePack::load(path)?;
+        app.open_puzzles();
+    } else if let Some(path) = &cli.edit {
+        let edited = if path.exists() {
+            board_file::load(path)?
+        } else {
+            // the mines are placed by hand, so only the size counts
+            let map_size = board.map_or(MapSize::Normal.map_size(), |(map_size, _)| map_size);
+            app.empty_board(map_size)
+        };
+        app.open_editor(edited, Some(path.clone()));
+    } else if let Some(path) = &cli.board_path {
+        app.load_board(board_file::load(path)?);
+    } else if let Some(port) = cli.host {
+        let (map_size, bomb_cnt) = board.unwrap_or((
+            MapSize::Normal.map_size(),
+            GameLevel::Normal.bomb_cnt(MapSize::Normal.map_size()),
+        ));
+        if cli.coop {
+            let listener = TcpListener::bind(("0.0.0.0", port))
+                .map_err(|e| anyhow!("cannot listen on port {}: {}", port, e))?;
+            app.host_coop(listener, map_size, bomb_cnt);
+        } else {
+            eprintln!("waiting for an opponent on port {}...", port);
+            let peer = Peer::host(port, Mode::Race)?;
+            app.host_race(peer, map_size, bomb_cnt)?;
+        }
+    } else if let Some(addr) = &cli.watch {
+        let peer = Peer::connect(addr.as_str(), Mode::Watch)
+            .map_err(|e| anyhow!("cannot watch the game at {}: {}", addr, e))?;
+        eprintln!("waiting for a game at {}...", addr);
+        app.watch(peer)?;
+    } else if let Some(addr) = &cli.connect {
+        let mode = if cli.coop { Mode::Coop } else { Mode::Race };
+        let peer = Peer::connect(addr.as_str(), mode)
+            .map_err(|e| anyhow!("cannot join the game at {}: {}", addr, e))?;
+        if cli.coop {
+            app.join_coop(peer)?;
+        } else {
+            app.join_race(peer)?;
+        }
+    } else if let Some((map_size, bomb_cnt)) = board {
+        app.init_mine_map(map_size, bomb_cnt);
+    }
+
+    if let Some(port) = cli.publish {
+        let ip = if cli.public { "0.0.0.0" } else { "127.0.0.1" };
+        let listener = TcpListener::bind((ip, port))
+            .map_err(|e| anyhow!("cannot publish on port {}: {}", port, e))?;
+        app.publish(listener);
+    }
 
     // Initialize the terminal user interface.
     let backend = CrosstermBackend::new(std::io::stderr());
@@ -31,20 +139,88 @@ fn main() -> Result<()> {
     let mut tui = Tui::new(terminal, events);
     tui.enter()?;
 
+    let result = run(&mut app, &mut tui);
+
+    // Exit the user interface.
+    tui.exit()?;
+    result
+}
+
+fn run(app: &mut App, tui: &mut Tui) -> Result<()> {
     // Start the main loop.
     while !app.should_quit {
         // Render the user interface.
-        tui.draw(&mut app)?;
+        tui.draw(app)?;
         // Handle events.
         match tui.events.next()? {
-            Event::Tick => {}
-            Event::Key(key_event) => update(&mut app, key_event),
-            Event::Mouse(_) => {}
+            Event::Tick => app.tick(),
+            Event::Key(key_event) => update(app, key_event),
+            Event::Mouse(mouse_event) => update_mouse(app, mouse_event),
             Event::Resize(_, _) => {}
         };
+
+        if let Some(feedback) = app.effects.sound.take() {
+            effects::play(app.effects.settings.sound, feedback, &mut std::io::stderr())?;
+        }
+
+        // a board that cannot be written is reported, the game goes on
+        if let Some(text) = app.export.take() {
+            if let Err(e) = write(board_file::exported_path(), text) {
+                app.warning = Some(format!("cannot export: {}", e));
+            }
+        }
+
+        if let Some(editor) = &mut app.editor {
+            if let Some(text) = editor.save.take() {
+                let path = match &editor.path {
+                    Some(path) => Ok(path.clone()),
+                    None => board_file::edited_path(),
+                };
+                if let Err(e) = write(path, text) {
+                    editor.saved = false;
+                    app.warning = Some(format!("cannot save: {}", e));
+                }
+            }
+        }
+
+        // so is a file that cannot be saved
+        if app.settings_unsaved {
+            app.settings_unsaved = false;
+            if let Err(e) = app.settings.save() {
+                app.warning = Some(format!("cannot save the settings: {}", e));
+            }
+        }
+
+        if app.puzzle_progress.unsaved {
+            if let Err(e) = app.puzzle_progress.save() {
+                app.puzzle_progress.unsaved = false;
+                app.warning = Some(format!("cannot save the puzzle progress: {}", e));
+            }
+        }
+
+        // Save finished games.
+        if let Some(result) = app.result.take() {
+            if let Err(e) = Stats::record(&result) {
+                app.warning = Some(format!("cannot save the statistics: {}", e));
+            }
+            if let Some(date) = result.daily {
+                app.daily_log.add(date, &result);
+                if let Err(e) = app.daily_log.save() {
+                    app.warning = Some(format!("cannot save the daily log: {}", e));
+                }
+            }
+            if let Some(recording) = &app.recording {
+                if let Err(e) = replay::last_replay_path().and_then(|path| recording.save(&path)) {
+                    app.warning = Some(format!("cannot save the replay: {}", e));
+                }
+            }
+        }
     }
 
-    // Exit the user interface.
-    tui.exit()?;
+    Ok(())
+}
+
+fn write(path: Result<PathBuf>, text: String) -> Result<()> {
+    std::fs::write(path?, text)?;
     Ok(())
 }
@@ -0,0 +1,54 @@
+use std::time::Duration;
+
+/// Lives of a [`GameMode::Lives`] game unless `--lives` says otherwise.
+pub const DEFAULT_LIVES: u32 = 3;
+
+/// Time attack: every game gets at least this long...
+const MIN_TIME: Duration = Duration::from_secs(30);
+/// ...and a second for every this many safe tiles.
+const SAFE_TILES_PER_SECOND: u32 = 4;
+/// Time added for every opening (an uncovered zero and the region around it).
+pub const OPENING_BONUS: Duration = Duration::from_secs(5);
+
+/// Rules a game is played by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GameMode {
+    /// The first mine loses.
+    #[default]
+    Classic,
+    /// Hit mines are shown and flagged and cost a life; the last life loses.
+    Lives,
+    /// The clock counts down and every opening adds some time; running out
+    /// of time loses, and so does a mine.
+    TimeAttack,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 3] = [GameMode::Classic, GameMode::Lives, GameMode::TimeAttack];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameMode::Classic => "classic",
+            GameMode::Lives => "lives",
+            GameMode::TimeAttack => "time-attack",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<GameMode> {
+        Self::ALL.into_iter().find(|mode| mode.name() == name)
+    }
+
+    /// The next mode, wrapping around.
+    pub fn next(&self) -> GameMode {
+        match self {
+            GameMode::Classic => GameMode::Lives,
+            GameMode::Lives => GameMode::TimeAttack,
+            GameMode::TimeAttack => GameMode::Classic,
+        }
+    }
+}
+
+/// Time a time attack game starts with on a board with `safe_tiles` safe tiles.
+pub fn time_limit(safe_tiles: u32) -> Duration {
+    MIN_TIME + Duration::from_secs((safe_tiles / SAFE_TILES_PER_SECOND) as u64)
+}
@@ -0,0 +1,425 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{Shutdown, TcpListener, TcpStream, ToSocketAddrs},
+    thread,
+};
+
+use anyhow::{anyhow, bail, Result};
+use crossbeam::channel::{self, Receiver};
+use minesweeper_engine::{Grid, Neighbourhood, Shape, Status, Tile, TileContent, TileCover};
+
+use crate::{
+    replay::{Action, Replay},
+    watch::View,
+};
+
+/// Bumped whenever a message changes meaning; both sides must speak the same version.
+pub const PROTOCOL_VERSION: u32 = 6;
+const HELLO: &str = "minesweeper-net";
+
+/// What two connected instances play.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Both solve the same board, first to clear it wins.
+    Race,
+    /// Everybody plays on one board kept by the host.
+    Coop,
+    /// Spectators follow the game of the host without playing.
+    Watch,
+}
+
+impl Mode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Mode::Race => "race",
+            Mode::Coop => "coop",
+            Mode::Watch => "watch",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Mode> {
+        match name {
+            "race" => Some(Mode::Race),
+            "coop" => Some(Mode::Coop),
+            "watch" => Some(Mode::Watch),
+            _ => None,
+        }
+    }
+}
+
+/// One line of the protocol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    /// First message of both sides.
+    Hello { version: u32, mode: Mode },
+    /// Board parameters, sent by the host. The events of the replay are not sent.
+    Board(Replay),
+    /// Safe tiles uncovered so far, out of all safe tiles.
+    Progress { revealed: u32, total: u32 },
+    /// The sender's game ended after this many milliseconds.
+    Finished { won: bool, ms: u64 },
+    /// Co-op: the host tells a guest its player number.
+    Welcome { player: u8 },
+    /// Co-op: a guest asks the host to do something, having seen the tile
+    /// with this cover (`None` if uncovered).
+    Act {
+        action: Action,
+        seen: Option<TileCover>,
+    },
+    /// Co-op: the host did this for a player; everybody applies it in this order.
+    Applied { player: u8, action: Action },
+    /// Co-op: a player left the game.
+    Left { player: u8 },
+    /// Watch: the game as the player sees it, sent whenever it changes.
+    View(View),
+    /// The sender left, or the connection broke.
+    Bye,
+}
+
+fn action_fields(action: &Action) -> (&'static str, u16, u16) {
+    match *action {
+        Action::Move(x, y) => ("move", x, y),
+        Action::Uncover(x, y) => ("uncover", x, y),
+        Action::ChangeCover(x, y) => ("cover", x, y),
+    }
+}
+
+fn parse_action(name: &str, x: &str, y: &str) -> Result<Action> {
+    let (x, y) = (x.parse()?, y.parse()?);
+    match name {
+        "move" => Ok(Action::Move(x, y)),
+        "uncover" => Ok(Action::Uncover(x, y)),
+        "cover" => Ok(Action::ChangeCover(x, y)),
+        _ => bail!("unknown action `{}`", name),
+    }
+}
+
+fn cover_name(cover: Option<TileCover>) -> String {
+    match cover {
+        None => "open".to_string(),
+        Some(TileCover::Empty) => "none".to_string(),
+        Some(TileCover::QuestionMark) => "?".to_string(),
+        Some(TileCover::FlagMark(n)) => format!("f{}", n),
+    }
+}
+
+/// A tile as one word: its cover, or what an uncovered tile shows.
+fn tile_name(tile: &Tile) -> String {
+    match (tile.cover, tile.content) {
+        (Some(cover), _) => cover_name(Some(cover)),
+        (None, TileContent::Empty(n)) => n.to_string(),
+        (None, TileContent::Bomb(n)) => format!("*{}", n),
+    }
+}
+
+fn parse_tile(name: &str) -> Result<Tile> {
+    let (content, cover) = match name.strip_prefix('*') {
+        Some(n) => (TileContent::Bomb(n.parse()?), None),
+        None if name.starts_with(|c: char| c.is_ascii_digit()) => {
+            (TileContent::Empty(name.parse()?), None)
+        }
+        None => (TileContent::Empty(0), parse_cover(name)?),
+    };
+    Ok(Tile { content, cover })
+}
+
+fn status_name(status: Status) -> &'static str {
+    match status {
+        Status::Playing => "playing",
+        Status::Won => "won",
+        Status::Lost => "lost",
+    }
+}
+
+fn parse_status(name: &str) -> Result<Status> {
+    match name {
+        "playing" => Ok(Status::Playing),
+        "won" => Ok(Status::Won),
+        "lost" => Ok(Status::Lost),
+        _ => bail!("unknown status `{}`", name),
+    }
+}
+
+fn parse_cover(name: &str) -> Result<Option<TileCover>> {
+    match name {
+        "open" => Ok(None),
+        "none" => Ok(Some(TileCover::Empty)),
+        "?" => Ok(Some(TileCover::QuestionMark)),
+        _ => match name.strip_prefix('f') {
+            Some(n) => Ok(Some(TileCover::FlagMark(n.parse()?))),
+            None => bail!("unknown cover `{}`", name),
+        },
+    }
+}
+
+impl Message {
+    pub fn to_line(&self) -> String {
+        match self {
+            Message::Hello { version, mode } => format!("{} {} {}", HELLO, version, mode.name()),
+            Message::Board(setup) => {
+                let (w, h) = setup.map_size;
+                format!(
+                    "board {} {} {} {} {} {} {} {} {} {} {} {}",
+                    w,
+                    h,
+                    setup.bomb_cnt,
+                    setup.seed,
+                    setup.no_guess as u8,
+                    setup.shape.name(),
+                    setup.wrap as u8,
+                    setup.max_mines,
+                    setup.neighbourhood.name(),
+                    setup.layers,
+                    setup.safe_first as u8,
+                    setup.question_marks as u8
+                )
+            }
+            Message::Progress { revealed, total } => format!("progress {} {}", revealed, total),
+            Message::Finished { won, ms } => {
+                format!("finished {} {}", if *won { "won" } else { "lost" }, ms)
+            }
+            Message::Welcome { player } => format!("welcome {}", player),
+            Message::Act { action, seen } => {
+                let (name, x, y) = action_fields(action);
+                format!("act {} {} {} {}", name, x, y, cover_name(*seen))
+            }
+            Message::Applied { player, action } => {
+                let (name, x, y) = action_fields(action);
+                format!("applied {} {} {} {}", player, name, x, y)
+            }
+            Message::Left { player } => format!("left {}", player),
+            Message::View(view) => {
+                let grid = view.grid;
+                let tiles: Vec<String> = view.tiles.iter().map(tile_name).collect();
+                format!(
+                    "view {} {} {} {} {} {} {} {} {} {} {} {} {}",
+                    grid.width,
+                    grid.height,
+                    grid.shape.name(),
+                    grid.wrap as u8,
+                    grid.neighbourhood.name(),
+                    grid.layers,
+                    view.cursor.0,
+                    view.cursor.1,
+                    view.secs,
+                    status_name(view.status),
+                    view.lives,
+                    view.time_left
+                        .map_or("-".to_string(), |left| left.to_string()),
+                    tiles.join(" ")
+                )
+            }
+            Message::Bye => "bye".to_string(),
+        }
+    }
+
+    pub fn from_line(line: &str) -> Result<Self> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let error = || anyhow!("malformed message `{}`", line);
+        let message = match fields[..] {
+            [HELLO, version, mode] => Message::Hello {
+                version: version.parse()?,
+                mode: Mode::from_name(mode).ok_or_else(error)?,
+            },
+            ["board", w, h, mines, seed, no_guess, shape, wrap, stack, neighbours, layers, safe_first, question_marks] =>
+            {
+                let mut setup = Replay::new(
+                    (w.parse()?, h.parse()?),
+                    mines.parse()?,
+                    seed.parse()?,
+                    no_guess == "1",
+                );
+                setup.shape = Shape::from_name(shape).ok_or_else(error)?;
+                setup.wrap = wrap == "1";
+                setup.max_mines = stack.parse()?;
+                setup.neighbourhood = Neighbourhood::from_name(neighbours).ok_or_else(error)?;
+                setup.layers = layers.parse()?;
+                setup.safe_first = safe_first == "1";
+                setup.question_marks = question_marks == "1";
+                setup
+                    .check()
+                    .map_err(|e| anyhow!("unplayable board `{}`: {}", line, e))?;
+                Message::Board(setup)
+            }
+            ["progress", revealed, total] => {
+                let (revealed, total) = (revealed.parse()?, total.parse()?);
+                if revealed > total {
+                    return Err(error());
+                }
+                Message::Progress { revealed, total }
+            }
+            ["finished", result @ ("won" | "lost"), ms] => Message::Finished {
+                won: result == "won",
+                ms: ms.parse()?,
+            },
+            ["welcome", player] => Message::Welcome {
+                player: player.parse()?,
+            },
+            ["act", name, x, y, seen] => Message::Act {
+                action: parse_action(name, x, y)?,
+                seen: parse_cover(seen)?,
+            },
+            ["applied", player, name, x, y] => Message::Applied {
+                player: player.parse()?,
+                action: parse_action(name, x, y)?,
+            },
+            ["left", player] => Message::Left {
+                player: player.parse()?,
+            },
+            ["view", w, h, shape, wrap, neighbours, layers, x, y, secs, status, lives, left, ref tiles @ ..] =>
+            {
+                let grid = Grid::new(
+                    w.parse()?,
+                    h.parse()?,
+                    Shape::from_name(shape).ok_or_else(error)?,
+                )
+                .with_wrap(wrap == "1")
+                .with_neighbourhood(Neighbourhood::from_name(neighbours).ok_or_else(error)?)
+                .with_layers(layers.parse()?);
+                // the same boards a replay may hold: layers stack plain square boards
+                let layers = grid.layers;
+                let plain = grid.shape == Shape::Square
+                    && !grid.wrap
+                    && grid.neighbourhood == Neighbourhood::Standard;
+                if !(1..=9).contains(&layers)
+                    || !grid.height.is_multiple_of(layers)
+                    || grid.layer_height() == 0
+                    || (layers > 1 && !plain)
+                {
+                    return Err(error());
+                }
+                let tiles = tiles
+                    .iter()
+                    .map(|name| parse_tile(name))
+                    .collect::<Result<Vec<_>>>()?;
+                let cursor: (u16, u16) = (x.parse()?, y.parse()?);
+                if tiles.len() != grid.len() || !grid.in_bounds(cursor.0 as i32, cursor.1 as i32) {
+                    return Err(error());
+                }
+                Message::View(View {
+                    grid,
+                    tiles,
+                    cursor,
+                    secs: secs.parse()?,
+                    status: parse_status(status)?,
+                    lives: lives.parse()?,
+                    time_left: match left {
+                        "-" => None,
+                        left => Some(left.parse()?),
+                    },
+                })
+            }
+            ["bye"] => Message::Bye,
+            _ => return Err(error()),
+        };
+        Ok(message)
+    }
+}
+
+/// The other instance, connected over TCP.
+///
+/// Incoming messages are read on a background thread; a broken connection
+/// shows up as a final [`Message::Bye`].
+#[derive(Debug)]
+pub struct Peer {
+    writer: TcpStream,
+    incoming: Receiver<Message>,
+}
+
+impl Peer {
+    /// Waits on `port` (all interfaces) for the other side to connect.
+    pub fn host(port: u16, mode: Mode) -> Result<Self> {
+        let listener = TcpListener::bind(("0.0.0.0", port))
+            .map_err(|e| anyhow!("cannot listen on port {}: {}", port, e))?;
+        Self::accept(&listener, mode)
+    }
+
+    /// Waits for the next connection on `listener`.
+    pub fn accept(listener: &TcpListener, mode: Mode) -> Result<Self> {
+        let (stream, _) = listener.accept()?;
+        Self::handshake(stream, mode)
+    }
+
+    pub fn connect(addr: impl ToSocketAddrs, mode: Mode) -> Result<Self> {
+        let stream = TcpStream::connect(addr)?;
+        Self::handshake(stream, mode)
+    }
+
+    /// Exchanges [`Message::Hello`] and starts reading in the background.
+    fn handshake(stream: TcpStream, mode: Mode) -> Result<Self> {
+        let mut writer = stream.try_clone()?;
+        let mut reader = BufReader::new(stream);
+
+        let hello = Message::Hello {
+            version: PROTOCOL_VERSION,
+            mode,
+        };
+        writeln!(writer, "{}", hello.to_line())?;
+
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        match Message::from_line(line.trim()) {
+            Ok(Message::Hello { version, .. }) if version != PROTOCOL_VERSION => bail!(
+                "the other side speaks protocol version {}, this is version {}",
+                version,
+                PROTOCOL_VERSION
+            ),
+            Ok(Message::Hello { mode: other, .. }) if other != mode => bail!(
+                "the other side wants to play {}, not {}",
+                other.name(),
+                mode.name()
+            ),
+            Ok(Message::Hello { .. }) => {}
+            _ => bail!("the other side is not a minesweeper instance"),
+        }
+
+        let (sender, incoming) = channel::unbounded();
+        thread::spawn(move || {
+            for line in reader.lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                match Message::from_line(&line) {
+                    Ok(message) => {
+                        if sender.send(message).is_err() {
+                            return;
+                        }
+                    }
+                    // nothing can be played without a board, so a bad one ends the connection
+                    Err(_) if line.starts_with("board ") => break,
+                    // unknown messages are skipped, the version check covers real mismatches
+                    Err(_) => {}
+                }
+            }
+            let _ = sender.send(Message::Bye);
+        });
+
+        Ok(Self { writer, incoming })
+    }
+
+    pub fn send(&mut self, message: &Message) -> Result<()> {
+        writeln!(self.writer, "{}", message.to_line())?;
+        Ok(())
+    }
+
+    /// The next message, if one arrived.
+    pub fn try_recv(&self) -> Option<Message> {
+        self.incoming.try_recv().ok()
+    }
+
+    /// Closes the connection, which also ends the reading thread.
+    pub fn close(&mut self) {
+        let _ = self.writer.shutdown(Shutdown::Both);
+    }
+
+    /// Waits for the next message.
+    pub fn recv(&self) -> Result<Message> {
+        Ok(self.incoming.recv()?)
+    }
+}
+
+impl Drop for Peer {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
@@ -0,0 +1,209 @@
+use std::{
+    collections::BTreeSet,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Result};
+use minesweeper_engine::{Board, TileCover};
+
+use crate::{board_file, storage};
+
+const PACK_HEADER: &str = "minesweeper-puzzles 1";
+const PROGRESS_FILE: &str = "puzzles.tsv";
+
+/// Pack shipped with the game: the classic patterns.
+const BUILTIN_NAME: &str = "basics";
+const BUILTIN: &str = include_str!("../puzzles/basics.puzzles");
+
+/// What a puzzle asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Goal {
+    /// Uncover every safe tile.
+    Clear,
+    /// Uncover this safe tile.
+    Find(u16, u16),
+}
+
+/// A board with some tiles already uncovered or flagged.
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    pub name: String,
+    pub goal: Goal,
+    /// the board as the puzzle starts
+    pub board: Board,
+}
+
+impl Puzzle {
+    /// Reads the lines of one puzzle, after its `puzzle <name>` line.
+    ///
+    /// Rows use the tiles of a [board file](board_file::to_text) plus `o` for
+    /// an uncovered safe tile, `F` for a flagged mine and `!` for the safe
+    /// tile to find. Without a `!` the board has to be cleared.
+    fn parse<'a>(name: &str, lines: impl Iterator<Item = (usize, &'a str)>) -> Result<Self> {
+        let (mut board, marks) = board_file::parse_body(lines, |ch| match ch {
+            'o' | '!' => Some(false),
+            'F' => Some(true),
+            _ => None,
+        })?;
+
+        let mut goal = Goal::Clear;
+        for ((x, y), mark) in marks {
+            match mark {
+                'o' => board.tile_mut(x, y).cover = None,
+                'F' => board.tile_mut(x, y).cover = Some(TileCover::FlagMark(1)),
+                _ if goal != Goal::Clear => bail!("more than one tile to find"),
+                _ => goal = Goal::Find(x, y),
+            }
+        }
+        if !board.tiles().iter().any(|t| t.is_covered() && !t.is_bomb()) {
+            bail!("every safe tile is already uncovered");
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            goal,
+            board,
+        })
+    }
+}
+
+/// Puzzles read from one file.
+#[derive(Debug, Clone, Default)]
+pub struct PuzzlePack {
+    pub name: String,
+    pub puzzles: Vec<Puzzle>,
+}
+
+impl PuzzlePack {
+    pub fn builtin() -> Self {
+        Self::from_text(BUILTIN_NAME, BUILTIN).expect("the built-in puzzles are valid")
+    }
+
+    /// Reads a pack: a header, then puzzles each starting with a `puzzle <name>` line.
+    pub fn from_text(name: &str, text: &str) -> Result<Self> {
+        let lines: Vec<(usize, &str)> = text.lines().enumerate().collect();
+        match lines.first() {
+            Some((_, PACK_HEADER)) => {}
+            _ => bail!("not a puzzle pack (expected `{}`)", PACK_HEADER),
+        }
+
+        let starts: Vec<usize> = (1..lines.len())
+            .filter(|&i| lines[i].1.starts_with("puzzle "))
+            .collect();
+        if starts.is_empty() {
+            bail!("the pack has no puzzles");
+        }
+        let preamble = &lines[1..starts[0]];
+        if let Some((i, line)) = preamble.iter().find(|(_, line)| {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with('#')
+        }) {
+            bail!("line {}: `{}` is not part of a puzzle", i + 1, line);
+        }
+
+        let mut puzzles = vec![];
+        for (k, &start) in starts.iter().enumerate() {
+            let end = starts.get(k + 1).copied().unwrap_or(lines.len());
+            let (i, line) = lines[start];
+            let puzzle_name = line["puzzle ".len()..].trim();
+            let puzzle = Puzzle::parse(puzzle_name, lines[start + 1..end].iter().copied())
+                .map_err(|e| anyhow!("puzzle `{}` (line {}): {}", puzzle_name, i + 1, e))?;
+            puzzles.push(puzzle);
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            puzzles,
+        })
+    }
+
+    /// Loads a pack named after the file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| anyhow!("cannot read puzzles {}: {}", path.display(), e))?;
+        let name = path
+            .file_stem()
+            .map_or("puzzles".into(), |stem| stem.to_string_lossy());
+        Self::from_text(&name, &text).map_err(|e| anyhow!("{}: {}", path.display(), e))
+    }
+}
+
+fn progress_path() -> Result<PathBuf> {
+    Ok(storage::data_dir()?.join(PROGRESS_FILE))
+}
+
+/// Which puzzles were solved, by pack and puzzle name.
+#[derive(Debug, Default)]
+pub struct PuzzleProgress {
+    pub solved: BTreeSet<(String, String)>,
+    /// solved puzzles were added since the last save
+    pub unsaved: bool,
+}
+
+impl PuzzleProgress {
+    /// Loads the progress, or no progress if there is none yet.
+    pub fn load() -> Result<Self> {
+        let text = match fs::read_to_string(progress_path()?) {
+            Ok(text) => text,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut solved = BTreeSet::new();
+        for (i, line) in text.lines().enumerate() {
+            let Some((pack, puzzle)) = line.split_once('\t') else {
+                return Err(anyhow!(
+                    "{}:{}: malformed puzzle line",
+                    PROGRESS_FILE,
+                    i + 1
+                ));
+            };
+            solved.insert((pack.to_string(), puzzle.to_string()));
+        }
+
+        Ok(Self {
+            solved,
+            unsaved: false,
+        })
+    }
+
+    pub fn save(&mut self) -> Result<()> {
+        let text: String = self
+            .solved
+            .iter()
+            .map(|(pack, puzzle)| format!("{}\t{}\n", pack, puzzle))
+            .collect();
+        fs::write(progress_path()?, text)?;
+        self.unsaved = false;
+        Ok(())
+    }
+
+    pub fn is_solved(&self, pack: &PuzzlePack, puzzle: &Puzzle) -> bool {
+        self.solved
+            .contains(&(pack.name.clone(), puzzle.name.clone()))
+    }
+
+    pub fn solve(&mut self, pack: &PuzzlePack, puzzle: &Puzzle) {
+        if self.solved.insert((pack.name.clone(), puzzle.name.clone())) {
+            self.unsaved = true;
+        }
+    }
+
+    /// Number of solved puzzles of a pack.
+    pub fn solved_in(&self, pack: &PuzzlePack) -> usize {
+        pack.puzzles
+            .iter()
+            .filter(|puzzle| self.is_solved(pack, puzzle))
+            .count()
+    }
+}
+
+/// The puzzle being played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PuzzleRun {
+    /// index in the pack
+    pub index: usize,
+    pub solved: bool,
+}
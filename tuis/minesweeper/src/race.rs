@@ -0,0 +1,80 @@
+use crate::net::{Message, Peer};
+
+/// Who won a race.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Winner {
+    Me,
+    Opponent,
+}
+
+/// A head-to-head race on the same board against a connected [`Peer`].
+///
+/// Clearing the board first wins, hitting a mine hands the win to the other side.
+#[derive(Debug)]
+pub struct Race {
+    peer: Peer,
+    /// (safe tiles the opponent uncovered, all safe tiles)
+    pub opponent: (u32, u32),
+    pub winner: Option<Winner>,
+    /// the opponent quit or the connection broke
+    pub opponent_left: bool,
+}
+
+impl Race {
+    pub fn new(peer: Peer) -> Self {
+        Self {
+            peer,
+            opponent: (0, 0),
+            winner: None,
+            opponent_left: false,
+        }
+    }
+
+    /// Sends our progress. A broken connection shows up as a [`Message::Bye`] later.
+    pub fn send_progress(&mut self, revealed: u32, total: u32) {
+        let _ = self.peer.send(&Message::Progress { revealed, total });
+    }
+
+    /// Our game ended.
+    pub fn finish(&mut self, won: bool, ms: u64) {
+        let _ = self.peer.send(&Message::Finished { won, ms });
+        if self.winner.is_none() {
+            self.winner = Some(if won { Winner::Me } else { Winner::Opponent });
+        }
+    }
+
+    /// Applies the messages the opponent sent since the last poll.
+    pub fn poll(&mut self) {
+        while let Some(message) = self.peer.try_recv() {
+            match message {
+                Message::Progress { revealed, total } => self.opponent = (revealed, total),
+                Message::Finished { won, .. } if self.winner.is_none() => {
+                    self.winner = Some(if won { Winner::Opponent } else { Winner::Me });
+                }
+                Message::Bye => {
+                    self.opponent_left = true;
+                    if self.winner.is_none() {
+                        self.winner = Some(Winner::Me);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Percentage of the board the opponent cleared.
+    pub fn opponent_percent(&self) -> u32 {
+        let (revealed, total) = self.opponent;
+        revealed
+            .saturating_mul(100)
+            .checked_div(total)
+            .unwrap_or(0)
+            .min(100)
+    }
+}
+
+impl Drop for Race {
+    fn drop(&mut self) {
+        let _ = self.peer.send(&Message::Bye);
+    }
+}
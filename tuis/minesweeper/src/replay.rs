@@ -0,0 +1,215 @@
+use std::{fmt::Write as _, fs, path::Path, path::PathBuf};
+
+use anyhow::{anyhow, bail, Result};
+use minesweeper_engine::{Grid, Neighbourhood, Shape};
+
+use crate::{cli::MAX_TILES, mode::GameMode, storage};
+
+const REPLAY_HEADER: &str = "minesweeper-replay 1";
+const LAST_REPLAY_FILE: &str = "last.replay";
+
+/// A player action, recorded with the cursor position it happened at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Move(u16, u16),
+    Uncover(u16, u16),
+    ChangeCover(u16, u16),
+}
+
+/// Everything needed to play a game back: the board parameters and the timed actions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Replay {
+    pub map_size: (u16, u16),
+    pub bomb_cnt: u32,
+    pub seed: u64,
+    pub no_guess: bool,
+    pub shape: Shape,
+    pub wrap: bool,
+    /// most mines on one tile
+    pub max_mines: u8,
+    pub neighbourhood: Neighbourhood,
+    /// layers of a three-dimensional board, each of `map_size`
+    pub layers: u16,
+    /// if the first uncovered tile holds a mine, the board is rebuilt with
+    /// that tile and its neighbours clear
+    pub safe_first: bool,
+    /// flag toggles go through the question mark
+    pub question_marks: bool,
+    pub mode: GameMode,
+    /// lives of a [`GameMode::Lives`] game
+    pub lives: u32,
+    /// (milliseconds since the game started, action)
+    pub events: Vec<(u64, Action)>,
+}
+
+/// Where the last finished game is saved.
+pub fn last_replay_path() -> Result<PathBuf> {
+    Ok(storage::data_dir()?.join(LAST_REPLAY_FILE))
+}
+
+impl Replay {
+    pub fn new(map_size: (u16, u16), bomb_cnt: u32, seed: u64, no_guess: bool) -> Self {
+        Self {
+            map_size,
+            bomb_cnt,
+            seed,
+            no_guess,
+            shape: Shape::Square,
+            wrap: false,
+            max_mines: 1,
+            neighbourhood: Neighbourhood::Standard,
+            layers: 1,
+            safe_first: false,
+            question_marks: true,
+            mode: GameMode::Classic,
+            lives: 1,
+            events: vec![],
+        }
+    }
+
+    /// The grid of the board, `layers` layers of `map_size` stacked into its rows.
+    pub fn grid(&self) -> Grid {
+        let (width, height) = self.map_size;
+        let layers = self.layers.max(1);
+        Grid::new(width, height.saturating_mul(layers), self.shape)
+            .with_wrap(self.wrap)
+            .with_neighbourhood(self.neighbourhood)
+            .with_layers(layers)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        let (w, h) = self.map_size;
+        let _ = writeln!(text, "{}", REPLAY_HEADER);
+        let _ = writeln!(text, "size {} {}", w, h);
+        let _ = writeln!(text, "mines {}", self.bomb_cnt);
+        let _ = writeln!(text, "seed {}", self.seed);
+        let _ = writeln!(text, "no-guess {}", self.no_guess as u8);
+        let _ = writeln!(text, "shape {}", self.shape.name());
+        let _ = writeln!(text, "wrap {}", self.wrap as u8);
+        let _ = writeln!(text, "stack {}", self.max_mines);
+        let _ = writeln!(text, "neighbours {}", self.neighbourhood.name());
+        let _ = writeln!(text, "layers {}", self.layers);
+        let _ = writeln!(text, "safe-first {}", self.safe_first as u8);
+        let _ = writeln!(text, "question-marks {}", self.question_marks as u8);
+        let _ = writeln!(text, "mode {}", self.mode.name());
+        let _ = writeln!(text, "lives {}", self.lives);
+        for (ms, action) in &self.events {
+            let (name, x, y) = match action {
+                Action::Move(x, y) => ("move", x, y),
+                Action::Uncover(x, y) => ("uncover", x, y),
+                Action::ChangeCover(x, y) => ("cover", x, y),
+            };
+            let _ = writeln!(text, "{} {} {} {}", ms, name, x, y);
+        }
+        text
+    }
+
+    pub fn from_text(text: &str) -> Result<Self> {
+        let mut lines = text.lines().enumerate();
+        match lines.next() {
+            Some((_, REPLAY_HEADER)) => {}
+            _ => bail!("not a replay file (expected `{}`)", REPLAY_HEADER),
+        }
+
+        let mut replay = Self {
+            max_mines: 1,
+            layers: 1,
+            question_marks: true,
+            lives: 1,
+            ..Self::default()
+        };
+        for (i, line) in lines {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let error = || anyhow!("line {}: malformed replay entry `{}`", i + 1, line);
+            match fields[..] {
+                [] => {}
+                ["size", w, h] => replay.map_size = (w.parse()?, h.parse()?),
+                ["mines", n] => replay.bomb_cnt = n.parse()?,
+                ["seed", n] => replay.seed = n.parse()?,
+                ["no-guess", n] => replay.no_guess = n == "1",
+                ["wrap", n] => replay.wrap = n == "1",
+                ["stack", n] => replay.max_mines = n.parse()?,
+                ["neighbours", name] => {
+                    replay.neighbourhood = Neighbourhood::from_name(name).ok_or_else(error)?
+                }
+                ["layers", n] => replay.layers = n.parse()?,
+                ["safe-first", n] => replay.safe_first = n == "1",
+                ["question-marks", n] => replay.question_marks = n == "1",
+                ["mode", name] => replay.mode = GameMode::from_name(name).ok_or_else(error)?,
+                ["lives", n] => replay.lives = n.parse()?,
+                ["shape", name] => replay.shape = Shape::from_name(name).ok_or_else(error)?,
+                [ms, name, x, y] => {
+                    let (x, y) = (x.parse()?, y.parse()?);
+                    let action = match name {
+                        "move" => Action::Move(x, y),
+                        "uncover" => Action::Uncover(x, y),
+                        "cover" => Action::ChangeCover(x, y),
+                        _ => return Err(error()),
+                    };
+                    replay.events.push((ms.parse()?, action));
+                }
+                _ => return Err(error()),
+            }
+        }
+
+        replay.check()?;
+        let grid = replay.grid();
+        for (ms, action) in &replay.events {
+            let (Action::Move(x, y) | Action::Uncover(x, y) | Action::ChangeCover(x, y)) = *action;
+            if !grid.in_bounds(x as i32, y as i32) {
+                bail!("event at {} ms is off the board: {},{}", ms, x, y);
+            }
+        }
+        Ok(replay)
+    }
+
+    /// Rejects board parameters no game can be played with, as `Cli::board` does.
+    pub fn check(&self) -> Result<()> {
+        let (w, h) = self.map_size;
+        if w < 2 || h < 2 {
+            bail!("the board must be at least 2x2");
+        }
+        if !(1..=9).contains(&self.layers) {
+            bail!("layers must be between 1 and 9");
+        }
+        if !(1..=9).contains(&self.max_mines) {
+            bail!("stack must be between 1 and 9");
+        }
+        // layers always touch the 26 tiles around, whatever the grid says
+        let plain = self.shape == Shape::Square
+            && !self.wrap
+            && self.neighbourhood == Neighbourhood::Standard;
+        if self.layers > 1 && !plain {
+            bail!("layered boards cannot be hex, wrap or use other neighbours");
+        }
+        // no-guess generation places one mine per tile
+        if self.no_guess && self.max_mines > 1 {
+            bail!("no-guess boards cannot stack mines");
+        }
+        if h.checked_mul(self.layers).is_none() {
+            bail!("the board is too tall for {} layers", self.layers);
+        }
+        // at least one tile has to stay safe
+        let tiles = w as u64 * h as u64 * self.layers as u64;
+        if tiles > MAX_TILES {
+            bail!("the board must have at most {} tiles", MAX_TILES);
+        }
+        let max = (tiles - 1) * self.max_mines as u64;
+        if self.bomb_cnt == 0 || self.bomb_cnt as u64 > max {
+            bail!("mines must be between 1 and {}", max);
+        }
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| anyhow!("cannot read replay {}: {}", path.display(), e))?;
+        Self::from_text(&text)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_text())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,235 @@
+use std::{fmt::Write as _, fs, io::ErrorKind, path::PathBuf};
+
+use anyhow::{anyhow, Result};
+
+use crate::{
+    effects::{EffectSettings, Feedback, Sound},
+    storage,
+    theme::{Glyphs, Theme},
+};
+
+const SETTINGS_FILE: &str = "settings.conf";
+
+/// One preference of the settings screen and the config file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Theme,
+    Glyphs,
+    SafeFirstClick,
+    ConfirmQuit,
+    QuestionMarks,
+    Sound,
+    CascadeFlash,
+    LossFlash,
+    WinAnimation,
+}
+
+impl Field {
+    pub const ALL: [Field; 9] = [
+        Field::Theme,
+        Field::Glyphs,
+        Field::SafeFirstClick,
+        Field::ConfirmQuit,
+        Field::QuestionMarks,
+        Field::Sound,
+        Field::CascadeFlash,
+        Field::LossFlash,
+        Field::WinAnimation,
+    ];
+
+    /// Name in the config file.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Field::Theme => "theme",
+            Field::Glyphs => "glyphs",
+            Field::SafeFirstClick => "safe-first-click",
+            Field::ConfirmQuit => "confirm-quit",
+            Field::QuestionMarks => "question-marks",
+            Field::Sound => "sound",
+            Field::CascadeFlash => "cascade-flash",
+            Field::LossFlash => "loss-flash",
+            Field::WinAnimation => "win-animation",
+        }
+    }
+
+    /// Name on the settings screen.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Field::Theme => "Theme",
+            Field::Glyphs => "Glyphs",
+            Field::SafeFirstClick => "Safe first click",
+            Field::ConfirmQuit => "Confirm quit",
+            Field::QuestionMarks => "Question marks",
+            Field::Sound => "Sound",
+            Field::CascadeFlash => "Cascade flash",
+            Field::LossFlash => "Loss flash",
+            Field::WinAnimation => "Win animation",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Field> {
+        Self::ALL.into_iter().find(|field| field.key() == key)
+    }
+}
+
+fn on_off(on: bool) -> &'static str {
+    if on {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+/// The next (or previous) of `values` after `current`, wrapping around.
+fn cycle<T: Copy + PartialEq>(values: &[T], current: T, forward: bool) -> T {
+    let i = values.iter().position(|&v| v == current).unwrap_or(0);
+    let n = values.len();
+    values[if forward {
+        (i + 1) % n
+    } else {
+        (i + n - 1) % n
+    }]
+}
+
+/// Preferences kept between runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Settings {
+    pub theme: Theme,
+    pub glyphs: Glyphs,
+    /// a mine under the first uncovered tile is moved away
+    pub safe_first_click: bool,
+    /// ask before quitting a game in progress
+    pub confirm_quit: bool,
+    /// flag toggles go through the question mark
+    pub question_marks: bool,
+    pub effects: EffectSettings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            theme: Theme::default(),
+            glyphs: Glyphs::default(),
+            safe_first_click: false,
+            confirm_quit: false,
+            question_marks: true,
+            effects: EffectSettings::default(),
+        }
+    }
+}
+
+fn settings_path() -> Result<PathBuf> {
+    Ok(storage::config_dir()?.join(SETTINGS_FILE))
+}
+
+impl Settings {
+    /// Value of a field as written in the config file.
+    pub fn value(&self, field: Field) -> &'static str {
+        match field {
+            Field::Theme => self.theme.name(),
+            Field::Glyphs => self.glyphs.name(),
+            Field::SafeFirstClick => on_off(self.safe_first_click),
+            Field::ConfirmQuit => on_off(self.confirm_quit),
+            Field::QuestionMarks => on_off(self.question_marks),
+            Field::Sound => self.effects.sound.name(),
+            Field::CascadeFlash => on_off(self.effects.visual(Feedback::Cascade)),
+            Field::LossFlash => on_off(self.effects.visual(Feedback::Loss)),
+            Field::WinAnimation => on_off(self.effects.visual(Feedback::Win)),
+        }
+    }
+
+    /// Sets a field from its config file value, rejecting values it cannot take.
+    pub fn set(&mut self, field: Field, value: &str) -> Result<()> {
+        let invalid = || anyhow!("invalid value `{}` for {}", value, field.key());
+        let flag = || match value {
+            "on" => Ok(true),
+            "off" => Ok(false),
+            _ => Err(invalid()),
+        };
+        match field {
+            Field::Theme => self.theme = Theme::from_name(value).ok_or_else(invalid)?,
+            Field::Glyphs => self.glyphs = Glyphs::from_name(value).ok_or_else(invalid)?,
+            Field::SafeFirstClick => self.safe_first_click = flag()?,
+            Field::ConfirmQuit => self.confirm_quit = flag()?,
+            Field::QuestionMarks => self.question_marks = flag()?,
+            Field::Sound => self.effects.sound = Sound::from_name(value).ok_or_else(invalid)?,
+            Field::CascadeFlash => self.effects.set_visual(Feedback::Cascade, flag()?),
+            Field::LossFlash => self.effects.set_visual(Feedback::Loss, flag()?),
+            Field::WinAnimation => self.effects.set_visual(Feedback::Win, flag()?),
+        }
+        Ok(())
+    }
+
+    /// Moves a field to its next (or previous) value.
+    pub fn cycle(&mut self, field: Field, forward: bool) {
+        match field {
+            Field::Theme => self.theme = cycle(&Theme::ALL, self.theme, forward),
+            Field::Glyphs => self.glyphs = cycle(&Glyphs::ALL, self.glyphs, forward),
+            Field::Sound => self.effects.sound = cycle(&Sound::ALL, self.effects.sound, forward),
+            _ => {
+                let on = self.value(field) == "on";
+                // every other field is on or off
+                let _ = self.set(field, on_off(!on));
+            }
+        }
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for field in Field::ALL {
+            let _ = writeln!(text, "{} = {}", field.key(), self.value(field));
+        }
+        text
+    }
+
+    /// Reads `key = value` lines; missing keys keep their default.
+    ///
+    /// Lines starting with `#` are comments. Malformed lines, unknown keys and
+    /// bad values are skipped, each with a warning.
+    pub fn from_text(text: &str) -> (Self, Vec<String>) {
+        let mut settings = Self::default();
+        let mut warnings = vec![];
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let warn = |message: String| format!("{}:{}: {}", SETTINGS_FILE, i + 1, message);
+            let Some((key, value)) = line.split_once('=') else {
+                warnings.push(warn("expected `key = value`".to_string()));
+                continue;
+            };
+            let Some(field) = Field::from_key(key.trim()) else {
+                warnings.push(warn(format!("unknown setting `{}`", key.trim())));
+                continue;
+            };
+            if let Err(e) = settings.set(field, value.trim()) {
+                warnings.push(warn(e.to_string()));
+            }
+        }
+        (settings, warnings)
+    }
+
+    /// Loads the settings, or the defaults if there is no config file yet.
+    ///
+    /// A file that cannot be read gives the defaults and a warning.
+    pub fn load() -> (Self, Vec<String>) {
+        let text = settings_path().and_then(|path| match fs::read_to_string(path) {
+            Ok(text) => Ok(text),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(String::new()),
+            Err(e) => Err(e.into()),
+        });
+        match text {
+            Ok(text) => Self::from_text(&text),
+            Err(e) => (
+                Self::default(),
+                vec![format!("cannot read {}: {}", SETTINGS_FILE, e)],
+            ),
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::write(settings_path()?, self.to_text())?;
+        Ok(())
+    }
+}
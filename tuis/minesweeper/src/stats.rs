@@ -0,0 +1,124 @@
+use std::{collections::BTreeMap, fs, io::ErrorKind, path::PathBuf, time::Duration};
+
+use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
+
+use crate::storage;
+
+const STATS_FILE: &str = "stats.tsv";
+
+/// Outcome of a finished game.
+#[derive(Debug, Clone)]
+pub struct GameResult {
+    /// board configuration, e.g. `40x30/200`
+    pub key: String,
+    pub won: bool,
+    pub time: Duration,
+    /// date of the daily challenge this game was
+    pub daily: Option<NaiveDate>,
+}
+
+/// Accumulated results of one board configuration.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Record {
+    pub played: u32,
+    pub won: u32,
+    pub best: Option<Duration>,
+}
+
+impl Record {
+    pub fn add(&mut self, result: &GameResult) {
+        self.played += 1;
+        if result.won {
+            self.won += 1;
+            self.best = Some(self.best.map_or(result.time, |best| best.min(result.time)));
+        }
+    }
+}
+
+/// Statistics of every board configuration played so far.
+#[derive(Debug, Default)]
+pub struct Stats {
+    pub records: BTreeMap<String, Record>,
+}
+
+fn stats_path() -> Result<PathBuf> {
+    Ok(storage::data_dir()?.join(STATS_FILE))
+}
+
+impl Stats {
+    /// Loads the statistics file, or empty statistics if there is none yet.
+    pub fn load() -> Result<Self> {
+        let text = match fs::read_to_string(stats_path()?) {
+            Ok(text) => text,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut records = BTreeMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let fields: Vec<&str> = line.split('\t').collect();
+            let [key, played, won, best_ms] = fields[..] else {
+                return Err(anyhow!("{}:{}: malformed stats line", STATS_FILE, i + 1));
+            };
+            let best_ms: u64 = best_ms.parse()?;
+            let record = Record {
+                played: played.parse()?,
+                won: won.parse()?,
+                best: (best_ms > 0).then(|| Duration::from_millis(best_ms)),
+            };
+            records.insert(key.to_string(), record);
+        }
+
+        Ok(Self { records })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let text: String = self
+            .records
+            .iter()
+            .map(|(key, r)| {
+                let best_ms = r.best.map_or(0, |best| best.as_millis());
+                format!("{}\t{}\t{}\t{}\n", key, r.played, r.won, best_ms)
+            })
+            .collect();
+        fs::write(stats_path()?, text)?;
+        Ok(())
+    }
+
+    pub fn add(&mut self, result: &GameResult) {
+        self.records
+            .entry(result.key.clone())
+            .or_default()
+            .add(result);
+    }
+
+    /// Adds a result to the statistics file.
+    pub fn record(result: &GameResult) -> Result<()> {
+        let mut stats = Self::load()?;
+        stats.add(result);
+        stats.save()
+    }
+
+    /// Formats the statistics as a plain text table.
+    pub fn table(&self) -> String {
+        let mut table = format!(
+            "{:<20} {:>8} {:>6} {:>6} {:>10}\n",
+            "board", "played", "won", "win%", "best"
+        );
+        for (key, r) in &self.records {
+            let rate = (r.won * 100).checked_div(r.played).unwrap_or(0);
+            let best = r.best.map_or("-".to_string(), |best| {
+                format!("{:.1}s", best.as_secs_f64())
+            });
+            table += &format!(
+                "{:<20} {:>8} {:>6} {:>5}% {:>10}\n",
+                key, r.played, r.won, rate, best
+            );
+        }
+        if self.records.is_empty() {
+            table += "no games played yet\n";
+        }
+        table
+    }
+}
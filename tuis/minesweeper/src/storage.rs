@@ -0,0 +1,41 @@
+use std::{env, fs, path::PathBuf};
+
+use anyhow::{anyhow, Result};
+
+/// Directory for persistent game data (statistics, replays, ...).
+///
+/// `$MINESWEEPER_DATA_DIR` wins, then `$XDG_DATA_HOME/minesweeper`,
+/// then `$HOME/.local/share/minesweeper`. The directory is created if missing.
+pub fn data_dir() -> Result<PathBuf> {
+    let dir = if let Some(dir) = env::var_os("MINESWEEPER_DATA_DIR") {
+        PathBuf::from(dir)
+    } else if let Some(dir) = env::var_os("XDG_DATA_HOME") {
+        PathBuf::from(dir).join("minesweeper")
+    } else if let Some(home) = env::var_os("HOME") {
+        PathBuf::from(home).join(".local/share/minesweeper")
+    } else {
+        return Err(anyhow!("cannot find a data directory: HOME is not set"));
+    };
+
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Directory for the configuration file.
+///
+/// `$MINESWEEPER_CONFIG_DIR` wins, then `$XDG_CONFIG_HOME/minesweeper`,
+/// then `$HOME/.config/minesweeper`. The directory is created if missing.
+pub fn config_dir() -> Result<PathBuf> {
+    let dir = if let Some(dir) = env::var_os("MINESWEEPER_CONFIG_DIR") {
+        PathBuf::from(dir)
+    } else if let Some(dir) = env::var_os("XDG_CONFIG_HOME") {
+        PathBuf::from(dir).join("minesweeper")
+    } else if let Some(home) = env::var_os("HOME") {
+        PathBuf::from(home).join(".config/minesweeper")
+    } else {
+        return Err(anyhow!("cannot find a config directory: HOME is not set"));
+    };
+
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
@@ -0,0 +1,261 @@
+use std::borrow::Cow;
+
+use minesweeper_engine::{Tile, TileContent, TileCover};
+use ratatui::style::{Color, Style};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Theme {
+    #[default]
+    Classic,
+    Dark,
+    Mono,
+    /// No colours at all, the terminal's own.
+    Plain,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 4] = [Theme::Classic, Theme::Dark, Theme::Mono, Theme::Plain];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Theme::Classic => "classic",
+            Theme::Dark => "dark",
+            Theme::Mono => "mono",
+            Theme::Plain => "plain",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Theme> {
+        Self::ALL.into_iter().find(|theme| theme.name() == name)
+    }
+}
+
+/// Symbols used to draw tiles.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Glyphs {
+    #[default]
+    Unicode,
+    /// Plain ASCII, for terminals and fonts without the unicode symbols.
+    Ascii,
+}
+
+impl Glyphs {
+    pub const ALL: [Glyphs; 2] = [Glyphs::Unicode, Glyphs::Ascii];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Glyphs::Unicode => "unicode",
+            Glyphs::Ascii => "ascii",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Glyphs> {
+        Self::ALL.into_iter().find(|glyphs| glyphs.name() == name)
+    }
+
+    fn covered(&self) -> &'static str {
+        match self {
+            Glyphs::Unicode => "ㅁ",
+            Glyphs::Ascii => " #",
+        }
+    }
+
+    fn flag(&self) -> &'static str {
+        match self {
+            Glyphs::Unicode => "⚑",
+            Glyphs::Ascii => "F",
+        }
+    }
+
+    /// Squeezes a tile symbol into one column, for the compact zoom.
+    ///
+    /// Symbols with a count keep their first character: stacked flags and
+    /// mines only show their count from the normal zoom up.
+    pub fn narrow(&self, symbol: &str) -> Cow<'static, str> {
+        if symbol == self.covered() {
+            return match self {
+                Glyphs::Unicode => "■".into(),
+                Glyphs::Ascii => "#".into(),
+            };
+        }
+        match symbol.trim_start().chars().next() {
+            Some(ch) => ch.to_string().into(),
+            None => " ".into(),
+        }
+    }
+}
+
+/// How many terminal cells a tile takes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Zoom {
+    /// One column, for big boards on small terminals.
+    Compact,
+    /// Two columns.
+    #[default]
+    Normal,
+    /// Four columns and two rows, with lines between the tiles.
+    Large,
+}
+
+impl Zoom {
+    pub const ALL: [Zoom; 3] = [Zoom::Compact, Zoom::Normal, Zoom::Large];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Zoom::Compact => "compact",
+            Zoom::Normal => "normal",
+            Zoom::Large => "large",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Zoom> {
+        Self::ALL.into_iter().find(|zoom| zoom.name() == name)
+    }
+
+    /// Columns and rows of one tile.
+    pub fn tile_size(&self) -> (u16, u16) {
+        match self {
+            Zoom::Compact => (1, 1),
+            Zoom::Normal => (2, 1),
+            Zoom::Large => (4, 2),
+        }
+    }
+
+    pub fn zoom_in(&self) -> Zoom {
+        match self {
+            Zoom::Compact => Zoom::Normal,
+            Zoom::Normal | Zoom::Large => Zoom::Large,
+        }
+    }
+
+    pub fn zoom_out(&self) -> Zoom {
+        match self {
+            Zoom::Compact | Zoom::Normal => Zoom::Compact,
+            Zoom::Large => Zoom::Normal,
+        }
+    }
+}
+
+/// Colors of one theme, as (background, foreground) pairs.
+struct Palette {
+    empty_cover: (Color, Color),
+    question_mark_cover: (Color, Color),
+    flag_mark_cover: (Color, Color),
+    bomb_content: (Color, Color),
+    empty_content_num_bg: [Color; 9],
+    empty_content_fg: Color,
+}
+
+const CLASSIC_PALETTE: Palette = Palette {
+    empty_cover: (Color::Rgb(180, 180, 180), Color::White),
+    question_mark_cover: (Color::Rgb(200, 200, 180), Color::Yellow),
+    flag_mark_cover: (Color::Rgb(200, 180, 180), Color::Red),
+    bomb_content: (Color::Rgb(250, 200, 200), Color::Red),
+    empty_content_num_bg: [
+        Color::Gray,
+        Color::LightBlue,
+        Color::LightRed,
+        Color::LightGreen,
+        Color::LightMagenta,
+        Color::LightCyan,
+        Color::LightYellow,
+        Color::LightBlue,
+        Color::LightRed,
+    ],
+    empty_content_fg: Color::Black,
+};
+
+const DARK_PALETTE: Palette = Palette {
+    empty_cover: (Color::Rgb(70, 70, 70), Color::Gray),
+    question_mark_cover: (Color::Rgb(80, 80, 50), Color::Yellow),
+    flag_mark_cover: (Color::Rgb(90, 50, 50), Color::LightRed),
+    bomb_content: (Color::Rgb(130, 30, 30), Color::White),
+    empty_content_num_bg: [
+        Color::Black,
+        Color::Blue,
+        Color::Red,
+        Color::Green,
+        Color::Magenta,
+        Color::Cyan,
+        Color::Yellow,
+        Color::Blue,
+        Color::Red,
+    ],
+    empty_content_fg: Color::White,
+};
+
+const MONO_PALETTE: Palette = Palette {
+    empty_cover: (Color::Gray, Color::Black),
+    question_mark_cover: (Color::Gray, Color::Black),
+    flag_mark_cover: (Color::Gray, Color::Black),
+    bomb_content: (Color::White, Color::Black),
+    empty_content_num_bg: [Color::Black; 9],
+    empty_content_fg: Color::White,
+};
+
+const PLAIN_PALETTE: Palette = Palette {
+    empty_cover: (Color::Reset, Color::Reset),
+    question_mark_cover: (Color::Reset, Color::Reset),
+    flag_mark_cover: (Color::Reset, Color::Reset),
+    bomb_content: (Color::Reset, Color::Reset),
+    empty_content_num_bg: [Color::Reset; 9],
+    empty_content_fg: Color::Reset,
+};
+
+fn style((bg, fg): (Color, Color)) -> Style {
+    Style::default().bg(bg).fg(fg)
+}
+
+impl Theme {
+    /// Symbol and style of a tile in this theme.
+    ///
+    /// Stacked flags and mines show their count, like `⚑2` and `*3`.
+    pub fn tile_symbol_n_style(&self, tile: &Tile, glyphs: Glyphs) -> (Cow<'static, str>, Style) {
+        let palette = match self {
+            Theme::Classic => &CLASSIC_PALETTE,
+            Theme::Dark => &DARK_PALETTE,
+            Theme::Mono => &MONO_PALETTE,
+            Theme::Plain => &PLAIN_PALETTE,
+        };
+
+        match &tile.cover {
+            Some(cover) => match cover {
+                TileCover::Empty => (glyphs.covered().into(), style(palette.empty_cover)),
+                TileCover::QuestionMark => (" ?".into(), style(palette.question_mark_cover)),
+                TileCover::FlagMark(1) => (
+                    format!(" {}", glyphs.flag()).into(),
+                    style(palette.flag_mark_cover),
+                ),
+                TileCover::FlagMark(n) => (
+                    format!("{}{}", glyphs.flag(), n).into(),
+                    style(palette.flag_mark_cover),
+                ),
+            },
+            None => match tile.content {
+                TileContent::Empty(num) => {
+                    let s: Cow<'static, str> = match num {
+                        0 => " .".into(),
+                        1 => " 1".into(),
+                        2 => " 2".into(),
+                        3 => " 3".into(),
+                        4 => " 4".into(),
+                        5 => " 5".into(),
+                        6 => " 6".into(),
+                        7 => " 7".into(),
+                        8 => " 8".into(),
+                        _ => format!("{:>2}", num).into(),
+                    };
+                    (
+                        s,
+                        style((
+                            palette.empty_content_num_bg[(num as usize).min(8)],
+                            palette.empty_content_fg,
+                        )),
+                    )
+                }
+                TileContent::Bomb(1) => (" *".into(), style(palette.bomb_content)),
+                TileContent::Bomb(n) => (format!("*{}", n).into(), style(palette.bomb_content)),
+            },
+        }
+    }
+}
@@ -1,17 +1,38 @@
 use ratatui::{
+    buffer::Buffer,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Padding, Paragraph, Wrap},
+    widgets::{
+        block::{Position, Title},
+        Block, BorderType, Borders, Padding, Paragraph, Wrap,
+    },
 };
 
+use chrono::Datelike;
+use minesweeper_engine::{Shape, Status, Tile};
+
 use crate::{
-    app::{App, MenuKind},
+    app::{App, BoardView, MenuKind},
+    daily::Calendar,
+    describe,
+    effects::Feedback,
+    mode::GameMode,
+    puzzle::Goal,
+    race::Winner,
+    settings::Field,
+    theme::{Theme, Zoom},
     tui::Frame,
 };
 
 pub fn render(app: &mut App, f: &mut Frame) {
-    if app.menu {
+    if let Some(calendar) = app.calendar {
+        render_calendar(app, &calendar, f);
+    } else if let Some(selected) = app.puzzle_select {
+        render_puzzles(app, selected, f);
+    } else if let Some(selected) = app.settings_select {
+        render_settings(app, selected, f);
+    } else if app.menu {
         render_menu(app, f);
     } else {
         render_game(app, f);
@@ -23,6 +44,11 @@ const MENU_NORMAL: &str = "NORMAL";
 const MENU_EASY: &str = "EASY";
 const MENU_LARGE: &str = "LARGE";
 const MENU_SMALL: &str = "SMALL";
+const MENU_EXPERT: &str = "EXPERT";
+const MENU_INTERMEDIATE: &str = "INTERMEDIATE";
+const MENU_BEGINNER: &str = "BEGINNER";
+const MENU_SETTINGS: &str = "Settings";
+const MENU_FOOTER: &str = "d: daily  p: puzzles";
 
 fn render_menu(app: &mut App, f: &mut Frame) {
     let frame_size = f.size();
@@ -36,14 +62,52 @@ fn render_menu(app: &mut App, f: &mut Frame) {
         .borders(Borders::ALL)
         .style(Style::default().bg(Color::White).fg(Color::Black))
         .padding(Padding::zero())
-        .title("Menu");
+        .title(match app.mode {
+            GameMode::Classic => "Menu".to_string(),
+            mode => format!("Menu: {}", mode.name()),
+        });
     let content_size = block.inner(size);
     f.render_widget(block, size);
 
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(content_size);
+    let settings_style = if let MenuKind::Settings = app.menu_focus {
+        Style::default().bg(Color::Black).fg(Color::White)
+    } else {
+        Style::default().bg(Color::White).fg(Color::Black)
+    };
+    let settings = Paragraph::new(Line::from(Span::styled(MENU_SETTINGS, settings_style)))
+        .style(Style::default().bg(Color::White))
+        .alignment(Alignment::Center);
+    f.render_widget(settings, rows[1]);
+    let footer = Paragraph::new(MENU_FOOTER)
+        .style(Style::default().bg(Color::White).fg(Color::Gray))
+        .alignment(Alignment::Center);
+    f.render_widget(footer, rows[2]);
+
+    // below the menu, as wide as the terminal
+    if let Some(warning) = &app.warning {
+        let y = size.y + size.height;
+        if y < frame_size.height {
+            let row = Rect {
+                y,
+                height: 1,
+                ..frame_size
+            };
+            f.render_widget(Paragraph::new(warning.as_str()), row);
+        }
+    }
+
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(content_size);
+        .split(rows[0]);
 
     let map_size_rect = chunks[0];
     let game_level_rect = chunks[1];
@@ -59,14 +123,16 @@ fn render_menu(app: &mut App, f: &mut Frame) {
     let selected_style = Style::default().fg(Color::Black);
     let unselected_style = Style::default().fg(Color::Gray);
 
+    // the classic sizes don't leave room for blank lines
     let mut map_sizes = vec![
         Line::from(MENU_LARGE),
-        Line::from(""),
         Line::from(MENU_NORMAL),
-        Line::from(""),
         Line::from(MENU_SMALL),
+        Line::from(MENU_EXPERT),
+        Line::from(MENU_INTERMEDIATE),
+        Line::from(MENU_BEGINNER),
     ];
-    map_sizes[app.menu_map_size as usize * 2]
+    map_sizes[app.menu_map_size as usize]
         .patch_style(Style::default().bg(Color::Black).fg(Color::White));
     let paragraph = Paragraph::new(map_sizes.clone())
         .style(Style::default().bg(Color::White))
@@ -88,10 +154,18 @@ fn render_menu(app: &mut App, f: &mut Frame) {
         Line::from(""),
         Line::from(MENU_EASY),
     ];
-    game_levels[app.menu_game_level as usize * 2]
-        .patch_style(Style::default().bg(Color::Black).fg(Color::White));
+    // a classic size comes with its level, so there is none to pick
+    let classic = app.menu_map_size.level().is_some();
+    if !classic {
+        game_levels[app.menu_game_level as usize * 2]
+            .patch_style(Style::default().bg(Color::Black).fg(Color::White));
+    }
     let paragraph = Paragraph::new(game_levels.clone())
-        .style(Style::default().bg(Color::White))
+        .style(if classic {
+            Style::default().bg(Color::White).fg(Color::Gray)
+        } else {
+            Style::default().bg(Color::White)
+        })
         .block(create_block("Game Level").title_style(
             if let MenuKind::GameLevel = app.menu_focus {
                 selected_style
@@ -104,70 +178,626 @@ fn render_menu(app: &mut App, f: &mut Frame) {
     f.render_widget(paragraph, game_level_rect);
 }
 
+fn render_calendar(app: &App, calendar: &Calendar, f: &mut Frame) {
+    let frame_size = f.size();
+    let size = Rect {
+        width: u16::min(28, frame_size.width),
+        height: u16::min(14, frame_size.height),
+        ..frame_size
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .style(Style::default().bg(Color::White).fg(Color::Black))
+        .title("Daily");
+
+    let mut lines = vec![
+        Line::styled(
+            calendar.month.format("%B %Y").to_string(),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Line::styled("Mo Tu We Th Fr Sa Su", Style::default().fg(Color::Gray)),
+    ];
+
+    // weeks start on monday, every month gets six rows so the layout stays put
+    let offset = calendar.month.weekday().num_days_from_monday() as usize;
+    let mut cells: Vec<Span> = vec![Span::raw("  "); offset];
+    for date in calendar.days() {
+        let mut style = Style::default();
+        if app.daily_log.completed(date) {
+            style = style.bg(Color::Green);
+        } else if app.daily_log.days.contains_key(&date) {
+            style = style.fg(Color::Red);
+        }
+        if date == calendar.today {
+            style = style.add_modifier(Modifier::REVERSED);
+        }
+        cells.push(Span::styled(format!("{:>2}", date.day()), style));
+    }
+    cells.resize(6 * 7, Span::raw("  "));
+    for week in cells.chunks(7) {
+        let mut spans = vec![];
+        for (i, cell) in week.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::raw(" "));
+            }
+            spans.push(cell.clone());
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let streak = app.daily_log.streak(calendar.today);
+    let today = match app.daily_log.days.get(&calendar.today) {
+        Some(record) => match record.best {
+            Some(best) => format!("today: best {:.1}s", best.as_secs_f64()),
+            None => format!("today: {} tries, not cleared", record.played),
+        },
+        None => "today: not played yet".to_string(),
+    };
+    lines.extend([
+        Line::from(""),
+        Line::from(format!(
+            "streak: {} day{}",
+            streak,
+            if streak == 1 { "" } else { "s" }
+        )),
+        Line::from(today),
+        Line::styled("Enter: play  h/l: month", Style::default().fg(Color::Gray)),
+    ]);
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Center);
+    f.render_widget(paragraph, size);
+}
+
+fn render_puzzles(app: &App, selected: usize, f: &mut Frame) {
+    let frame_size = f.size();
+    let pack = &app.puzzle_pack;
+    let size = Rect {
+        width: u16::min(36, frame_size.width),
+        height: u16::min(pack.puzzles.len() as u16 + 5, frame_size.height).min(20),
+        ..frame_size
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .style(Style::default().bg(Color::White).fg(Color::Black))
+        .title(format!("Puzzles: {}", pack.name));
+
+    // the list scrolls to keep the selected puzzle in view
+    let view = size.height.saturating_sub(5) as usize;
+    let first = selected.saturating_sub(view.saturating_sub(1));
+    let mut lines: Vec<Line> = pack
+        .puzzles
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(view)
+        .map(|(i, puzzle)| {
+            let mark = if app.puzzle_progress.is_solved(pack, puzzle) {
+                "✓"
+            } else {
+                " "
+            };
+            let mut line = Line::from(format!("{} {:>2}. {}", mark, i + 1, puzzle.name));
+            if i == selected {
+                line.patch_style(Style::default().bg(Color::Black).fg(Color::White));
+            }
+            line
+        })
+        .collect();
+
+    lines.extend([
+        Line::from(""),
+        Line::from(format!(
+            "solved {}/{}",
+            app.puzzle_progress.solved_in(pack),
+            pack.puzzles.len()
+        )),
+        Line::styled("Enter: play  Esc: back", Style::default().fg(Color::Gray)),
+    ]);
+
+    f.render_widget(Paragraph::new(lines).block(block), size);
+}
+
+fn render_settings(app: &App, selected: usize, f: &mut Frame) {
+    let frame_size = f.size();
+    let size = Rect {
+        width: u16::min(34, frame_size.width),
+        height: u16::min(Field::ALL.len() as u16 + 6, frame_size.height),
+        ..frame_size
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .style(Style::default().bg(Color::White).fg(Color::Black))
+        .title("Settings");
+
+    let highlight = |mut line: Line<'static>, i: usize| {
+        if i == selected {
+            line.patch_style(Style::default().bg(Color::Black).fg(Color::White));
+        }
+        line
+    };
+    let mut lines: Vec<Line> = Field::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let value = app.settings.value(*field);
+            highlight(Line::from(format!("{:<18}{:>12}", field.label(), value)), i)
+        })
+        .collect();
+    lines.extend([
+        Line::from(""),
+        highlight(Line::from("Reset to defaults"), Field::ALL.len()),
+        Line::from(""),
+        Line::styled("h/l: change  Esc: back", Style::default().fg(Color::Gray)),
+    ]);
+
+    f.render_widget(Paragraph::new(lines).block(block), size);
+}
+
 fn render_game(app: &mut App, f: &mut Frame) {
-    let (map_width, map_height) = app.map_size;
+    let grid = *app.game.board().grid();
+    // a three-dimensional board shows the layer of the cursor, starting at row `top`
+    let (map_width, map_height) = (grid.width, grid.layer_height());
+    let top = grid.layer(app.curr_pos.1) * map_height;
+    // odd rows of a hex board are drawn half a tile to the right
+    let hex = grid.shape == Shape::Hex;
+    let zoom = app.board_zoom();
+    let (tile_w, tile_h) = zoom.tile_size();
+    let hex_shift = hex as u16 * (tile_w / 2);
+    // the large zoom closes the lines between tiles on the right and bottom
+    let closing = (zoom == Zoom::Large) as u16;
     let frame_size = f.size();
     let size = Rect {
-        width: u16::min((map_width * 2) + 2, frame_size.width),
-        height: u16::min(map_height + 2, frame_size.height),
+        width: u16::min(
+            map_width
+                .saturating_mul(tile_w)
+                .saturating_add(2 + hex_shift + closing),
+            frame_size.width,
+        ),
+        // a race keeps a row for the opponent's progress, accessible mode
+        // keeps the rows of the status line and the description
+        height: u16::min(
+            map_height
+                .saturating_mul(tile_h)
+                .saturating_add(2 + closing),
+            frame_size
+                .height
+                .saturating_sub(app.race.is_some() as u16 + status_rows(app)),
+        ),
         ..frame_size
     };
 
+    // a double border hints that the edges of a torus connect
     let block = Block::default()
         .borders(Borders::ALL)
+        .border_type(if grid.wrap {
+            BorderType::Double
+        } else {
+            BorderType::Plain
+        })
         .style(Style::default())
         .padding(Padding::zero())
-        .title("Game");
+        .title({
+            let title = match (app.playback.is_some(), grid.wrap) {
+                (true, _) => "Replay".to_string(),
+                _ if app.watch.is_some() => "Watching".to_string(),
+                _ if app.editor.is_some() => {
+                    format!("Editor: {} mines", app.game.board().bomb_cnt())
+                }
+                _ if app.puzzle.is_some() => {
+                    let index = app.puzzle.map_or(0, |run| run.index);
+                    format!("Puzzle: {}", app.puzzle_pack.puzzles[index].name)
+                }
+                _ if app.coop.is_some() => {
+                    let players = app.coop.as_ref().map_or(1, |coop| coop.players());
+                    format!("Co-op ({} players)", players)
+                }
+                (false, true) => "Torus".to_string(),
+                (false, false) => "Game".to_string(),
+            };
+            let title = if app.flag_mode {
+                format!("{} (flag mode)", title)
+            } else {
+                title
+            };
+            let spectators = app.spectators.as_ref().map_or(0, |s| s.len());
+            let title = if spectators > 0 {
+                format!("{} ({} watching)", title, spectators)
+            } else {
+                title
+            };
+            let title = if grid.is_layered() {
+                format!("{} (layer {}/{})", title, top / map_height + 1, grid.layers)
+            } else {
+                title
+            };
+            match app.move_count {
+                Some(n) => format!("{} [{}]", title, n),
+                None => title,
+            }
+        });
+    // lives or time left, below the board; the clock of a watched game, what
+    // the editor has to say, a board that could not be written
+    let block = match (
+        &app.warning,
+        &app.editor,
+        &app.watch,
+        app.time_left(),
+        app.game.is_over(),
+    ) {
+        (Some(error), _, _, _, _) => {
+            block.title(Title::from(error.as_str()).position(Position::Bottom))
+        }
+        (_, Some(editor), _, _, _) => {
+            block.title(Title::from(editor.status()).position(Position::Bottom))
+        }
+        (_, _, Some(watch), _, _) => {
+            block.title(Title::from(watch.view.summary()).position(Position::Bottom))
+        }
+        (_, _, None, Some(left), false) => {
+            block.title(Title::from(format!("{}s left", left.as_secs())).position(Position::Bottom))
+        }
+        _ if app.game.lives() > 1 || !app.game.hits().is_empty() => block
+            .title(Title::from(format!("lives {}", app.game.lives())).position(Position::Bottom)),
+        _ => block,
+    };
     f.render_widget(block, size);
 
     let (map_ui_x, map_ui_y) = (size.x + 1, size.y + 1);
-    let (curr_x, curr_y) = app.curr_pos;
+    let (curr_x, curr_y) = (app.curr_pos.0, app.curr_pos.1 - top);
+
+    // only the tiles that fit are drawn, scrolled so the cursor stays in view
+    let (view_w, view_h) = (
+        size.width.saturating_sub(2 + hex_shift + closing) / tile_w,
+        size.height.saturating_sub(2 + closing) / tile_h,
+    );
+    let scroll = |curr: u16, view: u16, map: u16| {
+        curr.saturating_sub(view / 2).min(map.saturating_sub(view))
+    };
+    let (scroll_x, scroll_y) = (
+        scroll(curr_x, view_w, map_width),
+        scroll(curr_y, view_h, map_height),
+    );
+
+    app.board_view = Some(BoardView {
+        origin: (map_ui_x, map_ui_y),
+        scroll: (scroll_x, top + scroll_y),
+        tiles: (
+            map_width.min(scroll_x + view_w) - scroll_x,
+            map_height.min(scroll_y + view_h) - scroll_y,
+        ),
+        tile_size: (tile_w, tile_h),
+        hex,
+    });
 
     let buf = f.buffer_mut();
-    let mine_map = &app.mine_map;
+    let board = app.game.board();
+
+    // cursors of the other co-op players, each in its own colour
+    let other_cursors: Vec<(u8, (u16, u16))> = app.coop.as_ref().map_or(vec![], |coop| {
+        coop.cursors
+            .iter()
+            .filter(|(&player, _)| player != coop.me)
+            .map(|(&player, &pos)| (player, pos))
+            .collect()
+    });
 
-    for y in 0..map_height {
-        for x in 0..map_width {
-            let (symbol, mut style) = mine_map[y as usize][x as usize].symbol_n_style();
+    // the tile a puzzle asks to find
+    let target = app
+        .puzzle
+        .and_then(|run| match app.puzzle_pack.puzzles[run.index].goal {
+            Goal::Find(x, y) => Some((x, y)),
+            Goal::Clear => None,
+        });
 
-            if y == curr_y && x == curr_x {
-                let temp = style.bg;
-                style.bg = style.fg;
-                style.fg = temp;
+    for y in scroll_y..map_height.min(scroll_y + view_h) {
+        for x in scroll_x..map_width.min(scroll_x + view_w) {
+            let pos = (x, top + y);
+            let tile = board.tile(pos.0, pos.1);
+            // a mine hit in lives mode shows as a mine, marked red
+            let hit = app.game.hits().contains(&pos);
+            let shown = if hit {
+                Tile {
+                    cover: None,
+                    ..*tile
+                }
+            } else {
+                *tile
+            };
+            let (symbol, mut style) = app.theme.tile_symbol_n_style(&shown, app.glyphs);
+            if hit {
+                style = style.bg(Color::LightRed);
+            }
+            if target == Some(pos) && tile.is_covered() {
+                style = style.bg(Color::Yellow);
+            }
+            if let Some(bg) = effect_bg(app, pos.0, pos.1) {
+                style = style.bg(bg);
+            }
+
+            if y == curr_y && x == curr_x && app.theme == Theme::Plain {
+                style = style.add_modifier(Modifier::REVERSED);
+            } else if y == curr_y && x == curr_x {
+                std::mem::swap(&mut style.bg, &mut style.fg);
+            } else if let Some(player) = other_cursors.iter().find(|c| c.1 == pos) {
+                style = style.bg(player_color(player.0)).fg(Color::Black);
             }
 
-            let (ui_x, ui_y) = (map_ui_x + x * 2, map_ui_y + y);
-            if ui_x < size.x + size.width - 1 && ui_y < size.y + size.height - 1 {
-                buf.get_mut(ui_x, ui_y).set_symbol(symbol).set_style(style);
+            let shift = if y % 2 == 1 { hex_shift } else { 0 };
+            let (ui_x, ui_y) = (
+                map_ui_x + (x - scroll_x) * tile_w + shift,
+                map_ui_y + (y - scroll_y) * tile_h,
+            );
+            match zoom {
+                Zoom::Compact => {
+                    let symbol = app.glyphs.narrow(&symbol);
+                    buf.get_mut(ui_x, ui_y).set_symbol(&symbol).set_style(style);
+                }
+                Zoom::Normal => {
+                    buf.get_mut(ui_x, ui_y).set_symbol(&symbol).set_style(style);
+                }
+                Zoom::Large => {
+                    let corner = match (x == scroll_x, y == scroll_y) {
+                        (true, true) => "┌",
+                        (true, false) => "├",
+                        (false, true) => "┬",
+                        (false, false) => "┼",
+                    };
+                    buf.set_string(ui_x, ui_y, corner, Style::default());
+                    buf.set_string(ui_x + 1, ui_y, "───", Style::default());
+                    buf.set_string(ui_x, ui_y + 1, "│", Style::default());
+                    buf.get_mut(ui_x + 1, ui_y + 1)
+                        .set_symbol(&symbol)
+                        .set_style(style);
+                    buf.get_mut(ui_x + 3, ui_y + 1)
+                        .set_symbol(" ")
+                        .set_style(style);
+                }
             }
         }
     }
 
-    if app.over {
-        render_over(app, f);
+    if zoom == Zoom::Large {
+        if let Some(view) = app.board_view {
+            render_closing_lines(buf, view, hex_shift);
+        }
+    }
+
+    if let (true, Some(view)) = (grid.is_layered(), app.board_view) {
+        render_ghost_layers(app, f, size, view);
+    }
+
+    if app.race.is_some() {
+        render_race(app, f, size);
+    }
+
+    if app.accessible {
+        render_status(app, f, size);
+    }
+
+    if app.over() {
+        render_over(app, f, size);
+    } else if app.quitting {
+        render_message(f, size, " QUIT? (y/n) ", Color::White, Color::Black);
+    }
+}
+
+/// The lines right of and below the tiles of the large zoom, joined to the
+/// lines the tiles draw on their top and left.
+fn render_closing_lines(buf: &mut Buffer, view: BoardView, hex_shift: u16) {
+    let (tiles_w, tiles_h) = view.tiles;
+    let (ox, oy) = view.origin;
+    let shift = |dy: u16| {
+        if (view.scroll.1 + dy) % 2 == 1 {
+            hex_shift
+        } else {
+            0
+        }
+    };
+    for dy in 0..tiles_h {
+        let x = ox + tiles_w * 4 + shift(dy);
+        let joint = if dy == 0 { "┐" } else { "┤" };
+        buf.set_string(x, oy + dy * 2, joint, Style::default());
+        buf.set_string(x, oy + dy * 2 + 1, "│", Style::default());
+    }
+    let last = tiles_h.saturating_sub(1);
+    let y = oy + tiles_h * 2;
+    for dx in 0..tiles_w {
+        let joint = if dx == 0 { "└" } else { "┴" };
+        let x = ox + dx * 4 + shift(last);
+        buf.set_string(x, y, joint, Style::default());
+        buf.set_string(x + 1, y, "───", Style::default());
+    }
+    buf.set_string(ox + tiles_w * 4 + shift(last), y, "┘", Style::default());
+}
+
+/// The layers above and below the one shown of a three-dimensional board,
+/// dimmed, one cell per tile and scrolled like it, right of the board where they fit.
+fn render_ghost_layers(app: &App, f: &mut Frame, size: Rect, view: BoardView) {
+    let board = app.game.board();
+    let grid = board.grid();
+    let layer_height = grid.layer_height();
+    let layer = grid.layer(view.scroll.1);
+    let (tiles_w, tiles_h) = view.tiles;
+    let (scroll_x, scroll_y) = (view.scroll.0, view.scroll.1 - layer * layer_height);
+    let above = layer.checked_sub(1);
+    let below = Some(layer + 1).filter(|&below| below < grid.layers);
+
+    let mut x = size.right() + 1;
+    for ghost in [above, below].into_iter().flatten() {
+        let area = Rect {
+            x,
+            y: size.y,
+            width: tiles_w + 2,
+            height: tiles_h + 2,
+        };
+        let frame_size = f.size();
+        if area.right() > frame_size.right() || area.bottom() > frame_size.bottom() {
+            break;
+        }
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().add_modifier(Modifier::DIM))
+            .title((ghost + 1).to_string());
+        f.render_widget(block, area);
+
+        let buf = f.buffer_mut();
+        for dy in 0..tiles_h {
+            for dx in 0..tiles_w {
+                let tile = board.tile(scroll_x + dx, ghost * layer_height + scroll_y + dy);
+                let (symbol, style) = app.theme.tile_symbol_n_style(tile, app.glyphs);
+                buf.get_mut(area.x + 1 + dx, area.y + 1 + dy)
+                    .set_symbol(&app.glyphs.narrow(&symbol))
+                    .set_style(style.add_modifier(Modifier::DIM));
+            }
+        }
+        x = area.right() + 1;
+    }
+}
+
+/// Rows below the board taken by the accessible status line and description.
+fn status_rows(app: &App) -> u16 {
+    if !app.accessible {
+        return 0;
+    }
+    1 + app
+        .description
+        .as_ref()
+        .map_or(0, |lines| lines.len() as u16)
+}
+
+/// The tile under the cursor in words, and the description of its surroundings.
+fn render_status(app: &App, f: &mut Frame, area: Rect) {
+    let y = area.y + area.height + app.race.is_some() as u16;
+    let frame_size = f.size();
+    let height = status_rows(app).min(frame_size.height.saturating_sub(y));
+    if height == 0 {
         return;
     }
+
+    let mut lines = vec![Line::from(describe::status_line(app))];
+    if let Some(description) = &app.description {
+        lines.extend(description.iter().map(|line| Line::from(line.as_str())));
+    }
+    let rows = Rect {
+        x: frame_size.x,
+        y,
+        width: frame_size.width,
+        height,
+    };
+    f.render_widget(Paragraph::new(lines), rows);
+}
+
+const WIN_COLORS: [Color; 6] = [
+    Color::LightRed,
+    Color::LightYellow,
+    Color::LightGreen,
+    Color::LightCyan,
+    Color::LightBlue,
+    Color::LightMagenta,
+];
+
+/// Background of a tile while a visual effect runs: the board blinks on a big
+/// cascade (yellow) or a loss (red), a colour wave runs over it after a win.
+fn effect_bg(app: &App, x: u16, y: u16) -> Option<Color> {
+    let (feedback, ticks) = app.effects.active?;
+    let tile = app.game.board().tile(x, y);
+    match feedback {
+        Feedback::Cascade if ticks % 2 == 0 && !tile.is_covered() => Some(Color::LightYellow),
+        Feedback::Loss if ticks % 2 == 0 => Some(Color::Red),
+        Feedback::Win if !tile.is_bomb() => {
+            let wave = (x as usize + y as usize + WIN_COLORS.len() * 64 - ticks as usize)
+                % WIN_COLORS.len();
+            Some(WIN_COLORS[wave])
+        }
+        _ => None,
+    }
+}
+
+const PLAYER_COLORS: [Color; 6] = [
+    Color::Cyan,
+    Color::Magenta,
+    Color::Yellow,
+    Color::Green,
+    Color::Blue,
+    Color::LightRed,
+];
+
+/// Cursor colour of a co-op player.
+pub fn player_color(player: u8) -> Color {
+    PLAYER_COLORS[player as usize % PLAYER_COLORS.len()]
 }
 
-fn render_over(app: &mut App, f: &mut Frame) {
-    let (message, fg_color, bg_color) = if app.empty_cnt == 0 {
-        (" YOU WIN! ", Color::Yellow, Color::Black)
+/// The opponent's progress as a bar below the board.
+fn render_race(app: &App, f: &mut Frame, area: Rect) {
+    let Some(race) = &app.race else {
+        return;
+    };
+    let y = area.y + area.height;
+    if y >= f.size().height {
+        return;
+    }
+
+    let percent = race.opponent_percent().min(100);
+    let label = if race.opponent_left {
+        "opponent left".to_string()
     } else {
-        (" GAME OVER! ", Color::White, Color::Black)
+        format!("opponent {:>3}% ", percent)
     };
-    let len_msg = message.len() as u16;
-    let (map_w, map_h) = app.map_size;
-    let (mid_x, mid_y) = (1 + map_w / 2, 1 + map_h / 2);
-    let (over_x, over_y) = ((mid_x - len_msg / 2 + 1) * 2, mid_y - 1);
-    let chunk = {
-        let base_rect = Rect::default();
-        Rect {
-            x: over_x,
-            y: over_y,
-            width: len_msg + 2,
-            height: 3,
-            ..base_rect
+    let bar_width = area.width.saturating_sub(label.len() as u16) as u32;
+    let filled = (bar_width * percent / 100) as usize;
+    let line = Line::from(vec![
+        Span::raw(label),
+        Span::styled("█".repeat(filled), Style::default().fg(Color::Red)),
+        Span::styled(
+            "░".repeat((bar_width as usize).saturating_sub(filled)),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]);
+    let row = Rect {
+        y,
+        height: 1,
+        ..area
+    };
+    f.render_widget(Paragraph::new(line), row);
+}
+
+fn render_over(app: &mut App, f: &mut Frame, area: Rect) {
+    let host_left = app.coop.as_ref().is_some_and(|coop| coop.host_left);
+    let (message, fg_color, bg_color) = match app.race_winner() {
+        _ if host_left && !app.game.is_over() => (" HOST LEFT ", Color::White, Color::Black),
+        _ if app.watch.is_some() => (" PLAYER LEFT ", Color::White, Color::Black),
+        _ if app.puzzle.is_some_and(|run| run.solved) => (" SOLVED! ", Color::Yellow, Color::Black),
+        Some(Winner::Me) if app.game.status() != Status::Won => {
+            (" OPPONENT OUT, YOU WIN! ", Color::Yellow, Color::Black)
+        }
+        Some(Winner::Opponent) if app.game.status() != Status::Lost => {
+            (" OPPONENT WINS! ", Color::White, Color::Black)
         }
+        _ if app.game.status() == Status::Won => (" YOU WIN! ", Color::Yellow, Color::Black),
+        _ => (" GAME OVER! ", Color::White, Color::Black),
+    };
+    render_message(f, area, message, fg_color, bg_color);
+}
+
+/// A one-line message in a box centered on `area`.
+fn render_message(f: &mut Frame, area: Rect, message: &str, fg_color: Color, bg_color: Color) {
+    let len_msg = message.len() as u16;
+    let frame_size = f.size();
+    // centered on the board, with a blank column on each side
+    let (width, height) = (len_msg + 2, 3);
+    if frame_size.width < width + 2 || frame_size.height < height {
+        return;
+    }
+    let chunk = Rect {
+        x: (area.x + area.width.saturating_sub(width) / 2).clamp(1, frame_size.width - width - 1),
+        y: (area.y + area.height.saturating_sub(height) / 2).min(frame_size.height - height),
+        width,
+        height,
     };
 
     let block = Block::default()
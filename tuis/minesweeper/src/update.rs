@@ -1,8 +1,34 @@
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use minesweeper_engine::{HexDirection, Shape};
 
-use crate::app::{App, Movement};
+use crate::{
+    app::{App, MenuKind, Movement},
+    daily,
+};
 
 pub fn update(app: &mut App, key_event: KeyEvent) {
+    app.warning = None;
+
+    if app.calendar.is_some() {
+        update_calendar(app, key_event);
+        return;
+    }
+
+    if app.puzzle_select.is_some() {
+        update_puzzles(app, key_event);
+        return;
+    }
+
+    if app.settings_select.is_some() {
+        update_settings(app, key_event);
+        return;
+    }
+
+    if app.editor.is_some() {
+        update_editor(app, key_event);
+        return;
+    }
+
     if app.menu {
         update_menu(app, key_event);
         return;
@@ -18,23 +44,129 @@ pub fn update_menu(app: &mut App, key_event: KeyEvent) {
         KeyCode::Down | KeyCode::Char('j') => app.menu_move(Movement::Down),
         KeyCode::Left | KeyCode::Char('h') => app.menu_move(Movement::Left),
         KeyCode::Right | KeyCode::Char('l') => app.menu_move(Movement::Right),
+        KeyCode::Enter | KeyCode::Char('c') if matches!(app.menu_focus, MenuKind::Settings) => {
+            app.open_settings()
+        }
         KeyCode::Enter | KeyCode::Char('c') => {
-            let map_size = app.menu_map_size.map_size();
-            let bomb_cnt = app.menu_game_level.bomb_cnt(app.menu_map_size);
+            let (map_size, bomb_cnt) = app.menu_board();
             app.init_mine_map(map_size, bomb_cnt);
         }
+        KeyCode::Char('d') => app.open_calendar(daily::today()),
+        KeyCode::Char('p') => app.open_puzzles(),
+        KeyCode::Char('s') => app.open_settings(),
+        KeyCode::Char('m') => app.next_mode(),
+        KeyCode::Char('e') => {
+            let board = app.empty_board(app.menu_map_size.map_size());
+            app.open_editor(board, None);
+        }
+        _ => {}
+    }
+}
+
+/// The editor moves like a game; the uncover keys toggle mines instead.
+pub fn update_editor(app: &mut App, key_event: KeyEvent) {
+    let hex = app.game.board().grid().shape == Shape::Hex;
+    match key_event.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.reset(),
+        KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => app.quit(),
+        KeyCode::Char('c') | KeyCode::Enter | KeyCode::Char(' ') => app.editor_toggle_mine(),
+        KeyCode::Char('t') => app.editor_check(),
+        KeyCode::Char('s') => app.editor_save(),
+        KeyCode::Char('p') => app.editor_play(),
+        KeyCode::Up | KeyCode::Char('k') => app.game_move(Movement::Up),
+        KeyCode::Down | KeyCode::Char('j') => app.game_move(Movement::Down),
+        KeyCode::Left | KeyCode::Char('h') => app.game_move(Movement::Left),
+        KeyCode::Right | KeyCode::Char('l') => app.game_move(Movement::Right),
+        KeyCode::Char('w') if hex => app.game_move_hex(HexDirection::NorthWest),
+        KeyCode::Char('e') if hex => app.game_move_hex(HexDirection::NorthEast),
+        KeyCode::Char('a') if hex => app.game_move_hex(HexDirection::West),
+        KeyCode::Char('d') if hex => app.game_move_hex(HexDirection::East),
+        KeyCode::Char('z') if hex => app.game_move_hex(HexDirection::SouthWest),
+        KeyCode::Char('x') if hex => app.game_move_hex(HexDirection::SouthEast),
+        KeyCode::Char('g') => app.game_jump_edge(Movement::Up),
+        KeyCode::Char('G') => app.game_jump_edge(Movement::Down),
+        KeyCode::Char('0') | KeyCode::Home => app.game_jump_edge(Movement::Left),
+        KeyCode::Char('$') | KeyCode::End => app.game_jump_edge(Movement::Right),
+        KeyCode::Char('<') | KeyCode::PageUp => app.game_move_layer(true),
+        KeyCode::Char('>') | KeyCode::PageDown => app.game_move_layer(false),
+        KeyCode::Char('+') | KeyCode::Char('=') => app.zoom_in(),
+        KeyCode::Char('-') => app.zoom_out(),
+        _ => {}
+    }
+}
+
+pub fn update_settings(app: &mut App, key_event: KeyEvent) {
+    match key_event.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.close_settings(),
+        KeyCode::Up | KeyCode::Char('k') => app.settings_move(Movement::Up),
+        KeyCode::Down | KeyCode::Char('j') => app.settings_move(Movement::Down),
+        KeyCode::Left | KeyCode::Char('h') => app.settings_change(false),
+        KeyCode::Right | KeyCode::Char('l') | KeyCode::Enter | KeyCode::Char(' ') => {
+            app.settings_change(true)
+        }
+        _ => {}
+    }
+}
+
+pub fn update_puzzles(app: &mut App, key_event: KeyEvent) {
+    match key_event.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.close_puzzles(),
+        KeyCode::Up | KeyCode::Char('k') => app.puzzle_select_move(Movement::Up),
+        KeyCode::Down | KeyCode::Char('j') => app.puzzle_select_move(Movement::Down),
+        KeyCode::Enter | KeyCode::Char('c') => {
+            if let Some(selected) = app.puzzle_select {
+                app.start_puzzle(selected);
+            }
+        }
+        _ => {}
+    }
+}
+
+pub fn update_calendar(app: &mut App, key_event: KeyEvent) {
+    let Some(calendar) = &mut app.calendar else {
+        return;
+    };
+    match key_event.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.close_calendar(),
+        KeyCode::Left | KeyCode::Char('h') => calendar.prev_month(),
+        KeyCode::Right | KeyCode::Char('l') => calendar.next_month(),
+        KeyCode::Enter | KeyCode::Char('c') => {
+            let today = calendar.today;
+            app.start_daily(today);
+        }
         _ => {}
     }
 }
 
 pub fn update_game(app: &mut App, key_event: KeyEvent) {
-    if app.over {
+    if app.over() {
         update_over(app, key_event);
         return;
     }
 
+    // replays and watched games are only looked at
+    if app.playback.is_some() || app.watch.is_some() {
+        update_playback(app, key_event);
+        return;
+    }
+
+    if app.quitting {
+        app.quitting = false;
+        if key_event.code == KeyCode::Char('y') {
+            app.quit();
+        }
+        return;
+    }
+
+    app.description = None;
+    let pos = app.curr_pos;
+    let hex = app.game.board().grid().shape == Shape::Hex;
     match key_event.code {
-        KeyCode::Esc | KeyCode::Char('q') => app.quit(),
+        KeyCode::Char(c @ '0'..='9') if c != '0' || app.move_count.is_some() => {
+            app.push_move_count(c as u16 - '0' as u16);
+            return;
+        }
+        KeyCode::Esc | KeyCode::Char('q') => app.request_quit(),
         KeyCode::Char('c') | KeyCode::Char('C') => {
             if key_event.modifiers == KeyModifiers::CONTROL {
                 app.quit()
@@ -46,15 +178,104 @@ pub fn update_game(app: &mut App, key_event: KeyEvent) {
         KeyCode::Down | KeyCode::Char('j') => app.game_move(Movement::Down),
         KeyCode::Left | KeyCode::Char('h') => app.game_move(Movement::Left),
         KeyCode::Right | KeyCode::Char('l') => app.game_move(Movement::Right),
+        KeyCode::Char('w') if hex => app.game_move_hex(HexDirection::NorthWest),
+        KeyCode::Char('e') if hex => app.game_move_hex(HexDirection::NorthEast),
+        KeyCode::Char('a') if hex => app.game_move_hex(HexDirection::West),
+        KeyCode::Char('d') if hex => app.game_move_hex(HexDirection::East),
+        KeyCode::Char('z') if hex => app.game_move_hex(HexDirection::SouthWest),
+        KeyCode::Char('x') if hex => app.game_move_hex(HexDirection::SouthEast),
+        KeyCode::Char('K') => app.game_jump_covered(Movement::Up),
+        KeyCode::Char('J') => app.game_jump_covered(Movement::Down),
+        KeyCode::Char('H') => app.game_jump_covered(Movement::Left),
+        KeyCode::Char('L') => app.game_jump_covered(Movement::Right),
+        KeyCode::Char('g') => app.game_jump_edge(Movement::Up),
+        KeyCode::Char('G') => app.game_jump_edge(Movement::Down),
+        KeyCode::Char('0') | KeyCode::Home => app.game_jump_edge(Movement::Left),
+        KeyCode::Char('$') | KeyCode::End => app.game_jump_edge(Movement::Right),
+        KeyCode::Char('<') | KeyCode::PageUp => app.game_move_layer(true),
+        KeyCode::Char('>') | KeyCode::PageDown => app.game_move_layer(false),
+        KeyCode::Char('n') => app.game_jump_frontier(true),
+        KeyCode::Char('N') => app.game_jump_frontier(false),
         KeyCode::Enter => app.uncover_tile(),
         KeyCode::Char(' ') | KeyCode::Char('f') => app.change_cover(),
+        KeyCode::Char('m') => app.toggle_flag_mode(),
+        KeyCode::Char('+') | KeyCode::Char('=') => app.zoom_in(),
+        KeyCode::Char('-') => app.zoom_out(),
+        KeyCode::Char('E') => app.export_board(),
+        KeyCode::Char('i') if app.accessible => app.describe_surroundings(),
         _ => {}
     };
+
+    // a count prefix only applies to the key right after it
+    app.move_count = None;
+
+    if app.curr_pos != pos {
+        app.moved();
+    }
+}
+
+/// Clicks on the board: the left button uncovers (or flags, in flag mode),
+/// the right button changes the cover. The cursor moves to the clicked tile.
+/// In the editor the left button toggles a mine.
+pub fn update_mouse(app: &mut App, mouse_event: MouseEvent) {
+    let in_game = !app.menu
+        && app.calendar.is_none()
+        && app.puzzle_select.is_none()
+        && app.settings_select.is_none()
+        && app.playback.is_none()
+        && app.watch.is_none()
+        && !app.over()
+        && !app.quitting;
+    if !in_game {
+        return;
+    }
+    let Some(pos) = app
+        .board_view
+        .and_then(|view| view.tile_at(mouse_event.column, mouse_event.row))
+    else {
+        return;
+    };
+
+    if app.editor.is_some() {
+        if mouse_event.kind == MouseEventKind::Down(MouseButton::Left) {
+            app.curr_pos = pos;
+            app.editor_toggle_mine();
+        }
+        return;
+    }
+
+    match mouse_event.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            app.description = None;
+            app.click_move(pos);
+            app.uncover_tile();
+        }
+        MouseEventKind::Down(MouseButton::Right) => {
+            app.description = None;
+            app.click_move(pos);
+            app.change_cover();
+        }
+        _ => {}
+    }
+}
+
+pub fn update_playback(app: &mut App, key_event: KeyEvent) {
+    match key_event.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.quit(),
+        KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => app.quit(),
+        _ => {}
+    }
 }
 
 pub fn update_over(app: &mut App, key_event: KeyEvent) {
     match key_event.code {
+        // after a puzzle, back to the puzzle list
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter if app.puzzle.is_some() => {
+            app.reset();
+            app.open_puzzles();
+        }
         KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => app.reset(),
+        KeyCode::Char('E') => app.export_board(),
         _ => {}
     }
 }
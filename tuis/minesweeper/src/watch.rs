@@ -0,0 +1,172 @@
+use std::{net::TcpListener, thread};
+
+use anyhow::{bail, Result};
+use crossbeam::channel::{self, Receiver};
+use minesweeper_engine::{Board, GameState, Grid, Status, Tile, TileContent};
+
+use crate::net::{Message, Mode, Peer};
+
+/// What spectators see of a game: the board as the player sees it, the cursor
+/// and the clock.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct View {
+    pub grid: Grid,
+    /// every tile, row by row; covered tiles never tell what is under them
+    pub tiles: Vec<Tile>,
+    pub cursor: (u16, u16),
+    /// seconds since the game started
+    pub secs: u64,
+    pub status: Status,
+    pub lives: u32,
+    /// seconds left of a time attack game
+    pub time_left: Option<u64>,
+}
+
+impl View {
+    /// The view of `game`, with the contents of its covered tiles left out.
+    pub fn new(game: &GameState, cursor: (u16, u16), secs: u64, time_left: Option<u64>) -> Self {
+        let tiles = game
+            .board()
+            .tiles()
+            .iter()
+            .map(|tile| match tile.cover {
+                Some(_) => Tile {
+                    content: TileContent::Empty(0),
+                    ..*tile
+                },
+                None => *tile,
+            })
+            .collect();
+        Self {
+            grid: *game.board().grid(),
+            tiles,
+            cursor,
+            secs,
+            status: game.status(),
+            lives: game.lives(),
+            time_left,
+        }
+    }
+
+    /// A board to draw the view with.
+    pub fn board(&self) -> Board {
+        let mut board = Board::new(self.grid);
+        board.tiles_mut().copy_from_slice(&self.tiles);
+        board
+    }
+
+    /// The clock, lives or result, shown below the board.
+    pub fn summary(&self) -> String {
+        match (self.status, self.time_left) {
+            (Status::Won, _) => format!("won in {}s", self.secs),
+            (Status::Lost, _) => "lost".to_string(),
+            (Status::Playing, Some(left)) => format!("{}s left", left),
+            (Status::Playing, None) if self.lives > 1 => {
+                format!("{}s, lives {}", self.secs, self.lives)
+            }
+            (Status::Playing, None) => format!("{}s", self.secs),
+        }
+    }
+}
+
+/// Spectators following our game, joining on a listener at any time.
+#[derive(Debug)]
+pub struct Spectators {
+    /// spectators that connected since the last publish
+    joining: Receiver<Peer>,
+    peers: Vec<Peer>,
+    /// the view sent last, also sent to spectators joining later
+    pub last: Option<View>,
+}
+
+impl Spectators {
+    pub fn listen(listener: TcpListener) -> Self {
+        let (sender, joining) = channel::unbounded();
+        thread::spawn(move || {
+            while let Ok(peer) = Peer::accept(&listener, Mode::Watch) {
+                if sender.send(peer).is_err() {
+                    return;
+                }
+            }
+        });
+
+        Self {
+            joining,
+            peers: vec![],
+            last: None,
+        }
+    }
+
+    /// Number of spectators connected.
+    pub fn len(&self) -> usize {
+        self.peers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.peers.is_empty()
+    }
+
+    /// Sends `view` to every spectator if it changed, and the latest view to new ones.
+    pub fn publish(&mut self, view: View) {
+        // spectators only ever say goodbye
+        self.peers
+            .retain(|peer| !matches!(peer.try_recv(), Some(Message::Bye)));
+
+        let changed = self.last.as_ref() != Some(&view);
+        let message = Message::View(view.clone());
+        if changed {
+            for peer in self.peers.iter_mut() {
+                let _ = peer.send(&message);
+            }
+        }
+        while let Ok(mut peer) = self.joining.try_recv() {
+            let _ = peer.send(&message);
+            self.peers.push(peer);
+        }
+        self.last = Some(view);
+    }
+}
+
+/// Someone else's game followed read-only.
+#[derive(Debug)]
+pub struct Watch {
+    peer: Peer,
+    pub view: View,
+    /// the player quit or the connection broke
+    pub left: bool,
+}
+
+impl Watch {
+    /// Waits for the first view of the game.
+    pub fn join(peer: Peer) -> Result<Self> {
+        loop {
+            match peer.recv()? {
+                Message::View(view) => {
+                    return Ok(Self {
+                        peer,
+                        view,
+                        left: false,
+                    })
+                }
+                Message::Bye => bail!("the player left before a game started"),
+                _ => {}
+            }
+        }
+    }
+
+    /// Takes in the views that arrived since the last poll; whether there was one.
+    pub fn poll(&mut self) -> bool {
+        let mut changed = false;
+        while let Some(message) = self.peer.try_recv() {
+            match message {
+                Message::View(view) => {
+                    self.view = view;
+                    changed = true;
+                }
+                Message::Bye => self.left = true,
+                _ => {}
+            }
+        }
+        changed
+    }
+}
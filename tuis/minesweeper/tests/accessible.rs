@@ -0,0 +1,86 @@
+mod common;
+
+use common::{press, render};
+use minesweeper::{app::App, board_file, describe, theme::Theme};
+
+const BOARD: &str = "\
+minesweeper-board 1
+size 4 3
+*...
+....
+.**.
+";
+
+fn accessible_app() -> App {
+    let mut app = App::new();
+    app.accessible = true;
+    app.theme = Theme::Plain;
+    app.load_board(board_file::from_text(BOARD).unwrap());
+    app
+}
+
+#[test]
+fn status_line_announces_cursor_tile() {
+    let mut app = accessible_app();
+    assert_eq!(describe::status_line(&app), "row 1 col 2: covered");
+
+    // (1, 1) touches all three mines, flag two of them
+    press(&mut app, "jc");
+    assert_eq!(
+        describe::status_line(&app),
+        "row 2 col 2: 3, no flagged neighbours"
+    );
+    press(&mut app, "khfjjlfk");
+    assert_eq!(
+        describe::status_line(&app),
+        "row 2 col 2: 3, two flagged neighbours"
+    );
+
+    press(&mut app, "h");
+    assert_eq!(describe::status_line(&app), "row 2 col 1: covered");
+    press(&mut app, "k");
+    assert_eq!(describe::status_line(&app), "row 1 col 1: flagged");
+}
+
+#[test]
+fn status_line_is_drawn_below_board() {
+    let mut app = accessible_app();
+    press(&mut app, "l");
+
+    let screen = render(&mut app, 30, 8);
+    assert_eq!(screen[5].trim_end(), "row 1 col 3: covered");
+}
+
+#[test]
+fn describe_lists_surroundings_until_next_key() {
+    let mut app = accessible_app();
+    press(&mut app, "jcli");
+
+    let description = app.description.clone().unwrap();
+    assert_eq!(
+        description,
+        [
+            "row 1: col 2 covered, col 3 covered, col 4 covered",
+            "row 2: col 2 3, col 3 covered (cursor), col 4 covered",
+            "row 3: col 2 covered, col 3 covered, col 4 covered",
+        ]
+    );
+    let screen = render(&mut app, 60, 10);
+    assert_eq!(screen[6].trim_end(), description[0]);
+    assert_eq!(screen[8].trim_end(), description[2]);
+
+    press(&mut app, "h");
+    assert!(app.description.is_none());
+}
+
+#[test]
+fn corner_has_two_rows() {
+    let board = board_file::from_text(BOARD).unwrap();
+    assert_eq!(
+        describe::surroundings(&board, 0, 0),
+        [
+            "row 1: col 1 covered (cursor), col 2 covered",
+            "row 2: col 1 covered, col 2 covered"
+        ]
+    );
+}
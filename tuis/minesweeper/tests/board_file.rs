@@ -0,0 +1,91 @@
+mod common;
+
+use common::{press, seeded_game};
+use minesweeper::{app::App, board_file};
+use minesweeper_engine::{Shape, Status, TileContent};
+
+const BOARD: &str = "\
+minesweeper-board 1
+# a hand-made board
+size 5 3
+..*..
+.....
+*...2
+";
+
+#[test]
+fn numbers_are_computed_from_mines() {
+    let board = board_file::from_text(BOARD).unwrap();
+
+    assert_eq!(board.size(), (5, 3));
+    assert_eq!(board.bomb_cnt(), 4);
+    assert_eq!(board.max_mines(), 2);
+    assert_eq!(board.tile(1, 0).content, TileContent::Empty(1));
+    assert_eq!(board.tile(0, 1).content, TileContent::Empty(1));
+    assert_eq!(board.tile(3, 1).content, TileContent::Empty(3));
+    assert_eq!(board.tile(4, 2).content, TileContent::Bomb(2));
+}
+
+#[test]
+fn text_round_trip() {
+    let board = board_file::from_text(BOARD).unwrap();
+    let text = board_file::to_text(&board);
+
+    assert!(text.starts_with("minesweeper-board 1\nsize 5 3\n..*..\n"));
+    assert_eq!(
+        board_file::to_text(&board_file::from_text(&text).unwrap()),
+        text
+    );
+
+    let hex = "minesweeper-board 1\nsize 4 2\nshape hex\nwrap 1\n.*..\n...*\n";
+    let board = board_file::from_text(hex).unwrap();
+    assert_eq!(board.grid().shape, Shape::Hex);
+    assert!(board.grid().wrap);
+    assert_eq!(board_file::to_text(&board), hex);
+}
+
+#[test]
+fn malformed_boards_are_rejected() {
+    let reject = |text: &str| board_file::from_text(text).is_err();
+
+    assert!(reject("size 2 2\n..\n.*\n"));
+    assert!(reject("minesweeper-board 1\n..\n.*\n"));
+    assert!(reject("minesweeper-board 1\nsize 2 2\n..\n"));
+    assert!(reject("minesweeper-board 1\nsize 2 2\n..\n.*\n..\n"));
+    assert!(reject("minesweeper-board 1\nsize 2 2\n...\n.*\n"));
+    assert!(reject("minesweeper-board 1\nsize 2 2\n..\n.x\n"));
+    assert!(reject("minesweeper-board 1\nsize 2 2\n**\n**\n"));
+    assert!(reject(
+        "minesweeper-board 1\nsize 2 2\nshape round\n..\n.*\n"
+    ));
+}
+
+#[test]
+fn loaded_board_is_played_but_not_recorded() {
+    let mut app = App::new();
+    app.load_board(board_file::from_text(BOARD).unwrap());
+
+    assert!(!app.menu);
+    assert!(app.recording.is_none());
+
+    assert_eq!(app.curr_pos, (1, 0));
+    press(&mut app, "c");
+    assert!(!app.game.board().tile(1, 0).is_covered());
+    press(&mut app, "l");
+    press(&mut app, "c");
+    assert_eq!(app.game.status(), Status::Lost);
+    assert!(app.result.is_none());
+}
+
+#[test]
+fn export_writes_current_board() {
+    let mut app = seeded_game(7, (9, 9), 10);
+    press(&mut app, "E");
+
+    let text = app.export.take().unwrap();
+    let board = board_file::from_text(&text).unwrap();
+    assert_eq!(board.bomb_cnt(), 10);
+    for (loaded, played) in board.tiles().iter().zip(app.game.board().tiles()) {
+        assert_eq!(loaded.content, played.content);
+    }
+}
@@ -0,0 +1,219 @@
+use minesweeper::{
+    app::{GameLevel, MapSize},
+    cli::Cli,
+    effects::{Feedback, Sound},
+    mode::GameMode,
+    replay::{Action, Replay},
+    theme::Theme,
+};
+use minesweeper_engine::Shape;
+
+fn parse(args: &str) -> anyhow::Result<Cli> {
+    Cli::parse(args.split_whitespace().map(String::from))
+}
+
+#[test]
+fn no_options_start_at_menu() {
+    let cli = parse("").unwrap();
+
+    assert!(cli.board().unwrap().is_none());
+    assert!(!cli.help && !cli.stats && !cli.no_guess);
+}
+
+#[test]
+fn presets() {
+    let cli = parse("--size small -l easy").unwrap();
+
+    assert!(matches!(cli.size, Some(MapSize::Small)));
+    assert!(matches!(cli.level, Some(GameLevel::Easy)));
+    // 12.3% of the tiles
+    assert_eq!(cli.board().unwrap(), Some(((20, 15), 37)));
+}
+
+#[test]
+fn classic_presets() {
+    let board = |args: &str| parse(args).unwrap().board().unwrap().unwrap();
+
+    assert_eq!(board("--size beginner"), ((9, 9), 10));
+    assert_eq!(board("--size intermediate"), ((16, 16), 40));
+    assert_eq!(board("--size expert"), ((30, 16), 99));
+    // another level changes the density of a classic size
+    assert_eq!(board("--size expert --level easy"), ((30, 16), 59));
+}
+
+#[test]
+fn densities_fit_any_board() {
+    let board = |args: &str| parse(args).unwrap().board().unwrap().unwrap();
+
+    // the level's density without --mines
+    assert_eq!(board("-W 30 -H 16"), ((30, 16), 75));
+    assert_eq!(board("-W 30 -H 16 --level hard"), ((30, 16), 99));
+    assert_eq!(board("-W 100 -H 100 --density 17.5"), ((100, 100), 1750));
+    assert_eq!(board("--density=10"), ((40, 30), 120));
+    assert_eq!(board("-d 10 --layers 3 -W 10 -H 10"), ((10, 10), 30));
+    // a board keeps a mine and a safe tile
+    assert_eq!(board("-W 2 -H 2 -d 1"), ((2, 2), 1));
+    assert_eq!(board("-W 2 -H 2 -d 99"), ((2, 2), 3));
+
+    assert!(parse("--density 0").is_err());
+    assert!(parse("--density 100").is_err());
+    assert!(parse("--density lots").is_err());
+    assert!(parse("-d 20 -m 10").unwrap().board().is_err());
+}
+
+#[test]
+fn custom_board() {
+    let cli = parse("-W 30 --height=16 --mines 99 --seed 42 --theme dark --no-guess").unwrap();
+
+    assert_eq!(cli.board().unwrap(), Some(((30, 16), 99)));
+    assert_eq!(cli.seed, Some(42));
+    assert_eq!(cli.theme, Some(Theme::Dark));
+    assert!(cli.no_guess);
+}
+
+#[test]
+fn custom_board_needs_all_dimensions() {
+    assert!(parse("--width 30 --mines 10").unwrap().board().is_err());
+    assert!(parse("-W 5 -H 5 -m 25").unwrap().board().is_err());
+    assert!(parse("-W 1 -H 5 -m 1").unwrap().board().is_err());
+}
+
+#[test]
+fn conflicting_options() {
+    let board = |args: &str| parse(args).unwrap().board();
+
+    assert!(board("--replay a.replay --board b.board").is_err());
+    assert!(board("--edit a.board --host 7878").is_err());
+    assert!(board("--watch 7879 --connect 10.0.0.2:7878").is_err());
+    assert!(board("--watch 7879 --publish 7878").is_err());
+    assert!(board("--coop").is_err());
+    assert!(board("--host 7878 --coop").is_ok());
+    // boards made elsewhere take no board options
+    assert!(board("--replay a.replay --shape hex --wrap").is_err());
+    assert!(board("--board b.board --size small").is_err());
+    assert!(board("--puzzles p.puzzles --layers 2").is_err());
+    assert!(board("--connect 10.0.0.2:7878 --stack 2").is_err());
+    assert!(board("--edit a.board -W 12 -H 8 --shape hex").is_ok());
+    // the menu has sizes of odd height
+    assert!(board("--shape hex --wrap").is_err());
+    assert!(board("--shape hex --wrap --size normal").is_ok());
+    assert!(board("--stack 0 --replay a.replay").is_err());
+}
+
+#[test]
+fn board_shape_and_wrap() {
+    let cli = parse("--shape hex --wrap -W 10 -H 8 -m 10").unwrap();
+    assert_eq!(cli.shape, Some(Shape::Hex));
+    assert!(cli.wrap);
+    assert!(cli.board().unwrap().is_some());
+
+    assert!(parse("--shape hex --wrap -W 10 -H 7 -m 10")
+        .unwrap()
+        .board()
+        .is_err());
+    assert!(parse("--wrap -W 10 -H 7 -m 10").unwrap().board().is_ok());
+    assert!(parse("--shape round").is_err());
+}
+
+#[test]
+fn stacked_mines() {
+    let cli = parse("--stack 3 -W 4 -H 4 -m 45").unwrap();
+    assert_eq!(cli.stack, Some(3));
+    assert_eq!(cli.board().unwrap(), Some(((4, 4), 45)));
+
+    assert!(parse("--stack 3 -W 4 -H 4 -m 46").unwrap().board().is_err());
+    assert!(parse("--stack 0").unwrap().board().is_err());
+    assert!(parse("--stack 10").unwrap().board().is_err());
+    assert!(parse("--stack 2 --no-guess").unwrap().board().is_err());
+}
+
+#[test]
+fn invalid_options() {
+    assert!(parse("--bogus").is_err());
+    assert!(parse("--size huge").is_err());
+    assert!(parse("--mines lots").is_err());
+    assert!(parse("--seed").is_err());
+    assert!(parse("--theme neon").is_err());
+    // at most a million tiles
+    assert!(parse("-W 65535 -H 65535").unwrap().board().is_err());
+    assert!(parse("-W 1001 -H 1000").unwrap().board().is_err());
+    assert!(parse("-W 500 -H 500 --layers 5").unwrap().board().is_err());
+    assert!(parse("-W 1000 -H 1000").unwrap().board().is_ok());
+}
+
+#[test]
+fn replay_text_round_trip() {
+    // stacked mines don't go with no-guess
+    let mut replay = Replay::new((9, 9), 10, 1234, false);
+    replay.shape = Shape::Hex;
+    replay.wrap = true;
+    replay.max_mines = 4;
+    replay.events = vec![
+        (0, Action::Move(4, 5)),
+        (350, Action::Uncover(4, 5)),
+        (1200, Action::ChangeCover(0, 0)),
+    ];
+
+    let loaded = Replay::from_text(&replay.to_text()).unwrap();
+    assert_eq!(loaded.map_size, (9, 9));
+    assert_eq!(loaded.bomb_cnt, 10);
+    assert_eq!(loaded.seed, 1234);
+    assert!(!loaded.no_guess);
+    assert_eq!(loaded.shape, Shape::Hex);
+    assert!(loaded.wrap);
+    assert_eq!(loaded.max_mines, 4);
+    assert_eq!(loaded.events, replay.events);
+}
+
+#[test]
+fn replay_rejects_garbage() {
+    assert!(Replay::from_text("hello").is_err());
+    assert!(Replay::from_text("minesweeper-replay 1\nsize 9 9\n10 jump 1 1\n").is_err());
+}
+
+#[test]
+fn replay_rejects_impossible_games() {
+    let replay = |lines: &str| Replay::from_text(&format!("minesweeper-replay 1\n{}", lines));
+
+    assert!(replay("size 9 9\nmines 10\n10 uncover 8 8\n").is_ok());
+    assert!(replay("size 9 9\nmines 10\n10 uncover 9 4\n").is_err());
+    assert!(replay("size 9 9\nmines 10\n10 move 4 9\n").is_err());
+    assert!(replay("size 9 9\nmines 81\n").is_err());
+    assert!(replay("size 9 9\nmines 0\n").is_err());
+    assert!(replay("size 9 9\nmines 10\nlayers 0\n").is_err());
+    assert!(replay("size 9 9\nmines 10\nlayers 10\n").is_err());
+    assert!(replay("size 9 9\nmines 10\nstack 10\n").is_err());
+    assert!(replay("size 9 9\nmines 10\nstack 2\nno-guess 1\n").is_err());
+    // the rows of every layer count
+    assert!(replay("size 9 9\nmines 10\nlayers 3\n10 cover 4 26\n").is_ok());
+}
+
+#[test]
+fn sound_and_effects() {
+    let cli = parse("--sound bell --effects win,loss").unwrap();
+    assert_eq!(cli.sound, Some(Sound::Bell));
+    assert_eq!(cli.effects, Some(vec![Feedback::Win, Feedback::Loss]));
+
+    assert_eq!(parse("--effects none").unwrap().effects, Some(vec![]));
+    assert!(parse("--effects sparkles").is_err());
+    assert!(parse("--sound loud").is_err());
+}
+
+#[test]
+fn game_modes() {
+    let cli = parse("--mode lives --lives 5").unwrap();
+    assert_eq!(cli.mode, Some(GameMode::Lives));
+    assert_eq!(cli.lives, Some(5));
+
+    assert_eq!(
+        parse("--mode time-attack").unwrap().mode,
+        Some(GameMode::TimeAttack)
+    );
+    assert!(parse("--mode zen").is_err());
+    assert!(parse("--lives 0").is_err());
+
+    // --lives alone picks lives mode, and is no use with the others
+    assert_eq!(parse("--lives 2").unwrap().mode, Some(GameMode::Lives));
+    assert!(parse("--mode classic --lives 2").is_err());
+    assert!(parse("--lives 2 --mode time-attack").is_err());
+}
@@ -0,0 +1,143 @@
+//! Drives an [`App`] with scripted keys and renders it without a terminal.
+
+#![allow(dead_code)]
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use minesweeper::{
+    app::App,
+    ui,
+    update::{update, update_mouse},
+};
+use ratatui::{backend::TestBackend, buffer::Buffer, Terminal};
+use unicode_width::UnicodeWidthStr;
+
+/// Turns a script like `"5l<Enter>jj f"` into key events.
+///
+/// `<Name>` is a special key (`Enter`, `Esc`, `Space`, `Up`, `Down`, `Left`,
+/// `Right`, `Home`, `End`, `PageUp`, `PageDown`), anything else is a typed character.
+pub fn keys(script: &str) -> Vec<KeyEvent> {
+    let mut events = vec![];
+    let mut chars = script.chars();
+    while let Some(ch) = chars.next() {
+        let code = if ch == '<' {
+            let name: String = chars.by_ref().take_while(|&c| c != '>').collect();
+            match name.as_str() {
+                "Enter" => KeyCode::Enter,
+                "Esc" => KeyCode::Esc,
+                "Space" => KeyCode::Char(' '),
+                "Up" => KeyCode::Up,
+                "Down" => KeyCode::Down,
+                "Left" => KeyCode::Left,
+                "Right" => KeyCode::Right,
+                "Home" => KeyCode::Home,
+                "End" => KeyCode::End,
+                "PageUp" => KeyCode::PageUp,
+                "PageDown" => KeyCode::PageDown,
+                _ => panic!("unknown key <{}>", name),
+            }
+        } else {
+            KeyCode::Char(ch)
+        };
+        let modifiers = match code {
+            KeyCode::Char(c) if c.is_ascii_uppercase() => KeyModifiers::SHIFT,
+            _ => KeyModifiers::NONE,
+        };
+        events.push(KeyEvent::new(code, modifiers));
+    }
+    events
+}
+
+/// Feeds a key script to the app through [`update`].
+pub fn press(app: &mut App, script: &str) {
+    for key_event in keys(script) {
+        update(app, key_event);
+    }
+}
+
+/// Presses a mouse button on a terminal cell through [`update_mouse`].
+pub fn click(app: &mut App, button: MouseButton, column: u16, row: u16) {
+    update_mouse(
+        app,
+        MouseEvent {
+            kind: MouseEventKind::Down(button),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        },
+    );
+}
+
+/// An app already in a game on a seeded board.
+pub fn seeded_game(seed: u64, map_size: (u16, u16), bomb_cnt: u32) -> App {
+    let mut app = App::new();
+    app.seed = Some(seed);
+    app.init_mine_map(map_size, bomb_cnt);
+    app
+}
+
+/// Uncovers every safe tile by moving the cursor there and pressing `c`.
+pub fn clear_board(app: &mut App) {
+    let (w, h) = app.map_size();
+    for y in 0..h {
+        for x in 0..w {
+            let tile = app.game.board().tile(x, y);
+            if tile.is_covered() && !tile.is_bomb() {
+                app.curr_pos = (x, y);
+                press(app, "c");
+            }
+        }
+    }
+}
+
+/// Position of some bomb on the board.
+pub fn find_bomb(app: &App) -> (u16, u16) {
+    let board = app.game.board();
+    let idx = board
+        .tiles()
+        .iter()
+        .position(|tile| tile.is_bomb())
+        .expect("board without bombs");
+    board.position(idx)
+}
+
+pub fn render_buffer(app: &mut App, width: u16, height: u16) -> Buffer {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(|f| ui::render(app, f)).unwrap();
+    terminal.backend().buffer().clone()
+}
+
+/// Renders the app and returns the screen as text, one string per row.
+///
+/// Cells hidden behind a wide symbol are skipped, like a terminal would show them.
+pub fn render(app: &mut App, width: u16, height: u16) -> Vec<String> {
+    let buffer = render_buffer(app, width, height);
+    let mut lines = vec![];
+    for y in 0..height {
+        let mut line = String::new();
+        let mut skip = 0;
+        for x in 0..width {
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            let symbol = &buffer.get(x, y).symbol;
+            line.push_str(symbol);
+            skip = symbol.width().saturating_sub(1);
+        }
+        lines.push(line.trim_end().to_string());
+    }
+    lines
+}
+
+/// Compares the rendered screen with the expected rows (trailing spaces ignored).
+#[track_caller]
+pub fn assert_screen(app: &mut App, width: u16, height: u16, expected: &[&str]) {
+    let actual = render(app, width, height);
+    let expected: Vec<String> = expected.iter().map(|l| l.trim_end().to_string()).collect();
+    assert_eq!(
+        actual,
+        expected,
+        "\nactual screen:\n{}\n",
+        actual.join("\n")
+    );
+}
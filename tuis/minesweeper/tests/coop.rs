@@ -0,0 +1,235 @@
+mod common;
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener},
+    thread,
+    time::{Duration, Instant},
+};
+
+use common::{find_bomb, press, render_buffer};
+use minesweeper::{
+    app::App,
+    net::{Message, Mode, Peer, PROTOCOL_VERSION},
+    replay::Action,
+    ui,
+};
+use minesweeper_engine::{Status, TileCover};
+
+/// A host with its address, playing a 10x8 board.
+fn host() -> (App, SocketAddr) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let mut host = App::new();
+    host.seed = Some(21);
+    host.host_coop(listener, (10, 8), 10);
+    (host, addr)
+}
+
+fn join(host: &mut App, addr: SocketAddr) -> App {
+    let guest = thread::spawn(move || {
+        let mut app = App::new();
+        app.join_coop(Peer::connect(addr, Mode::Coop).unwrap())
+            .unwrap();
+        app
+    });
+    // the host sends the board once it picks the new player up
+    while !guest.is_finished() {
+        host.tick();
+        thread::sleep(Duration::from_millis(1));
+    }
+    guest.join().unwrap()
+}
+
+/// Ticks everybody until `done` holds, failing after a second.
+fn sync(apps: &mut [&mut App], done: impl Fn(&[&mut App]) -> bool) {
+    let start = Instant::now();
+    while !done(apps) {
+        assert!(start.elapsed() < Duration::from_secs(1), "timed out");
+        thread::sleep(Duration::from_millis(2));
+        for app in apps.iter_mut() {
+            app.tick();
+        }
+    }
+}
+
+fn cover(app: &App, (x, y): (u16, u16)) -> Option<TileCover> {
+    app.game.board().tile(x, y).cover
+}
+
+#[test]
+fn messages_round_trip() {
+    let messages = [
+        Message::Welcome { player: 3 },
+        Message::Act {
+            action: Action::ChangeCover(4, 5),
+            seen: Some(TileCover::FlagMark(2)),
+        },
+        Message::Act {
+            action: Action::Uncover(0, 1),
+            seen: None,
+        },
+        Message::Applied {
+            player: 2,
+            action: Action::Move(7, 7),
+        },
+        Message::Left { player: 1 },
+    ];
+
+    for message in messages {
+        assert_eq!(Message::from_line(&message.to_line()).unwrap(), message);
+    }
+}
+
+#[test]
+fn guests_share_the_board_and_flags() {
+    let (mut host, addr) = host();
+    let mut guest = join(&mut host, addr);
+    assert_eq!(host.game.board().tiles(), guest.game.board().tiles());
+
+    guest.curr_pos = (0, 0);
+    press(&mut guest, "f");
+    // nothing changes until the host applied it
+    assert_eq!(cover(&guest, (0, 0)), Some(TileCover::Empty));
+
+    sync(&mut [&mut host, &mut guest], |apps| {
+        apps.iter()
+            .all(|app| app.game.board().tile(0, 0).is_flagged())
+    });
+}
+
+#[test]
+fn first_request_on_a_tile_wins() {
+    let (mut host, addr) = host();
+    let mut alice = join(&mut host, addr);
+    let mut bob = join(&mut host, addr);
+
+    // both see an unflagged tile and flag it at the same time
+    for guest in [&mut alice, &mut bob] {
+        guest.curr_pos = (9, 7);
+        press(guest, "f");
+    }
+    sync(&mut [&mut host, &mut alice, &mut bob], |apps| {
+        apps.iter()
+            .all(|app| app.game.board().tile(9, 7).is_flagged())
+    });
+    thread::sleep(Duration::from_millis(20));
+    for app in [&mut host, &mut alice, &mut bob] {
+        app.tick();
+    }
+
+    // the second toggle was dropped instead of turning the flag into a question mark
+    for app in [&host, &alice, &bob] {
+        assert_eq!(cover(app, (9, 7)), Some(TileCover::FlagMark(1)));
+    }
+}
+
+#[test]
+fn late_players_catch_up() {
+    let (mut host, addr) = host();
+    host.curr_pos = (0, 0);
+    press(&mut host, "f");
+    host.curr_pos = (5, 3);
+    press(&mut host, "c");
+
+    let mut guest = join(&mut host, addr);
+    sync(&mut [&mut host, &mut guest], |apps| {
+        apps[1].game.empty_cnt() == apps[0].game.empty_cnt()
+    });
+    assert_eq!(cover(&guest, (0, 0)), Some(TileCover::FlagMark(1)));
+}
+
+#[test]
+fn one_bad_reveal_loses_for_everybody() {
+    let (mut host, addr) = host();
+    let mut guest = join(&mut host, addr);
+
+    guest.curr_pos = find_bomb(&guest);
+    press(&mut guest, "c");
+    sync(&mut [&mut host, &mut guest], |apps| {
+        apps.iter().all(|app| app.game.status() == Status::Lost)
+    });
+}
+
+#[test]
+fn other_cursors_are_coloured() {
+    let (mut host, addr) = host();
+    let mut guest = join(&mut host, addr);
+
+    guest.curr_pos = (1, 1);
+    guest.moved();
+    sync(&mut [&mut host, &mut guest], |apps| {
+        apps[0].coop.as_ref().unwrap().cursors.get(&1) == Some(&(1, 1))
+    });
+
+    let coop = host.coop.as_ref().unwrap();
+    assert_eq!(coop.players(), 2);
+    let buffer = render_buffer(&mut host, 30, 12);
+    assert_eq!(buffer.get(1 + 2, 2).bg, ui::player_color(1));
+    let screen = common::render(&mut host, 30, 12);
+    assert!(screen[0].starts_with("┌Co-op (2 players)"));
+}
+
+#[test]
+fn moves_off_the_board_are_dropped() {
+    let (mut host, addr) = host();
+    let mut guest = join(&mut host, addr);
+
+    guest
+        .coop
+        .as_mut()
+        .unwrap()
+        .request(Action::Move(50, 50), None);
+    for _ in 0..20 {
+        thread::sleep(Duration::from_millis(5));
+        host.tick();
+    }
+    assert_ne!(host.coop.as_ref().unwrap().cursors.get(&1), Some(&(50, 50)));
+
+    guest
+        .coop
+        .as_mut()
+        .unwrap()
+        .request(Action::Move(2, 3), None);
+    sync(&mut [&mut host, &mut guest], |apps| {
+        apps[0].coop.as_ref().unwrap().cursors.get(&1) == Some(&(2, 3))
+    });
+    assert!(!guest.coop.as_ref().unwrap().host_left);
+    assert_eq!(host.coop.as_ref().unwrap().players(), 2);
+}
+
+#[test]
+fn host_leaving_ends_the_game() {
+    let (mut host, addr) = host();
+    let mut guest = join(&mut host, addr);
+    drop(host);
+
+    sync(&mut [&mut guest], |apps| apps[0].over());
+    assert!(guest.coop.as_ref().unwrap().host_left);
+}
+
+#[test]
+fn guests_leave_a_host_sending_tiles_off_the_board() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let host = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        writeln!(stream, "minesweeper-net {} coop", PROTOCOL_VERSION).unwrap();
+        writeln!(stream, "welcome 1").unwrap();
+        writeln!(stream, "board 10 8 10 21 0 square 0 1 standard 1 0 1").unwrap();
+        writeln!(stream, "applied 0 uncover 50 50").unwrap();
+        // until the guest hangs up
+        let mut line = String::new();
+        let mut reader = BufReader::new(stream);
+        while reader.read_line(&mut line).unwrap() > 0 {}
+    });
+
+    let mut guest = App::new();
+    guest
+        .join_coop(Peer::connect(addr, Mode::Coop).unwrap())
+        .unwrap();
+    sync(&mut [&mut guest], |apps| apps[0].over());
+    assert!(guest.coop.as_ref().unwrap().host_left);
+    assert_eq!(guest.game.board().bomb_cnt(), 10);
+    host.join().unwrap();
+}
@@ -0,0 +1,160 @@
+mod common;
+
+use std::time::Duration;
+
+use chrono::NaiveDate;
+use common::{assert_screen, clear_board, press, render_buffer};
+use minesweeper::{
+    app::App,
+    daily::{self, Calendar, DailyLog},
+    stats::GameResult,
+};
+use minesweeper_engine::Shape;
+use ratatui::style::{Color, Modifier};
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+fn result(won: bool, secs: u64, daily: NaiveDate) -> GameResult {
+    GameResult {
+        key: "daily".to_string(),
+        won,
+        time: Duration::from_secs(secs),
+        daily: Some(daily),
+    }
+}
+
+#[test]
+fn seed_depends_only_on_the_date() {
+    assert_eq!(
+        daily::seed(date(2026, 10, 18)),
+        daily::seed(date(2026, 10, 18))
+    );
+    assert_ne!(
+        daily::seed(date(2026, 10, 18)),
+        daily::seed(date(2026, 10, 19))
+    );
+    // pinned, so every build generates the same daily boards
+    assert_eq!(daily::seed(date(2026, 10, 18)), 12714201419439376771);
+}
+
+#[test]
+fn daily_board_ignores_board_options() {
+    let mut app = App::new();
+    app.shape = Shape::Hex;
+    app.wrap = true;
+    app.max_mines = 3;
+    app.no_guess = true;
+    app.start_daily(date(2026, 10, 18));
+
+    let mut other = App::new();
+    other.start_daily(date(2026, 10, 18));
+
+    let grid = app.game.board().grid();
+    assert_eq!(
+        (grid.width, grid.height, grid.shape, grid.wrap),
+        (20, 15, Shape::Square, false)
+    );
+    assert_eq!(app.game.board().bomb_cnt(), 35);
+    assert_eq!(app.game.board().tiles(), other.game.board().tiles());
+}
+
+#[test]
+fn finished_daily_reports_its_date() {
+    let mut app = App::new();
+    app.start_daily(date(2026, 10, 18));
+    clear_board(&mut app);
+
+    let result = app.result.take().unwrap();
+    assert!(result.won);
+    assert_eq!(result.key, "daily");
+    assert_eq!(result.daily, Some(date(2026, 10, 18)));
+}
+
+#[test]
+fn log_keeps_best_time_per_date() {
+    let mut log = DailyLog::default();
+    let today = date(2026, 10, 18);
+    log.add(today, &result(false, 10, today));
+    log.add(today, &result(true, 90, today));
+    log.add(today, &result(true, 60, today));
+
+    let record = log.days[&today];
+    assert_eq!((record.played, record.won), (3, 2));
+    assert_eq!(record.best, Some(Duration::from_secs(60)));
+}
+
+#[test]
+fn streak_counts_days_in_a_row() {
+    let mut log = DailyLog::default();
+    for day in [12, 14, 15, 16, 17] {
+        let d = date(2026, 10, day);
+        log.add(d, &result(true, 30, d));
+    }
+    let lost = date(2026, 10, 13);
+    log.add(lost, &result(false, 30, lost));
+
+    // today is not played yet, so the streak up to yesterday still counts
+    assert_eq!(log.streak(date(2026, 10, 18)), 4);
+    assert_eq!(log.streak(date(2026, 10, 17)), 4);
+    assert_eq!(log.streak(date(2026, 10, 19)), 0);
+    assert_eq!(log.streak(date(2026, 10, 12)), 1);
+}
+
+#[test]
+fn calendar_does_not_go_past_this_month() {
+    let mut calendar = Calendar::new(date(2026, 10, 18));
+    assert_eq!(calendar.month, date(2026, 10, 1));
+    assert_eq!(calendar.days().count(), 31);
+
+    calendar.next_month();
+    assert_eq!(calendar.month, date(2026, 10, 1));
+    calendar.prev_month();
+    calendar.prev_month();
+    assert_eq!(calendar.month, date(2026, 8, 1));
+    calendar.next_month();
+    assert_eq!(calendar.month, date(2026, 9, 1));
+}
+
+#[test]
+fn calendar_screen() {
+    let mut app = App::new();
+    for day in [15, 16, 17] {
+        let d = date(2026, 10, day);
+        app.daily_log.add(d, &result(true, 42, d));
+    }
+    app.open_calendar(date(2026, 10, 18));
+
+    assert_screen(
+        &mut app,
+        30,
+        14,
+        &[
+            "┌Daily─────────────────────┐",
+            "│       October 2026       │",
+            "│   Mo Tu We Th Fr Sa Su   │",
+            "│             1  2  3  4   │",
+            "│    5  6  7  8  9 10 11   │",
+            "│   12 13 14 15 16 17 18   │",
+            "│   19 20 21 22 23 24 25   │",
+            "│   26 27 28 29 30 31      │",
+            "│                          │",
+            "│                          │",
+            "│      streak: 3 days      │",
+            "│   today: not played yet  │",
+            "│  Enter: play  h/l: month │",
+            "└──────────────────────────┘",
+        ],
+    );
+
+    // completed days are green, today is highlighted
+    let buffer = render_buffer(&mut app, 30, 14);
+    assert_eq!(buffer.get(13, 5).bg, Color::Green);
+    assert_eq!(buffer.get(10, 5).bg, Color::White);
+    assert!(buffer.get(22, 5).modifier.contains(Modifier::REVERSED));
+
+    press(&mut app, "<Enter>");
+    assert!(app.calendar.is_none());
+    assert_eq!(app.daily, Some(date(2026, 10, 18)));
+}
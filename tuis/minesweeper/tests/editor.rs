@@ -0,0 +1,154 @@
+mod common;
+
+use common::{press, render};
+use minesweeper::{app::App, board_file, cli::Cli, editor::Check};
+use minesweeper_engine::{Board, Grid, TileContent};
+
+/// The editor on an empty 3x4 board, the cursor in the middle of the top row.
+fn editor_app() -> App {
+    let mut app = App::new();
+    app.open_editor(Board::new(Grid::square(3, 4)), None);
+    app.curr_pos = (1, 0);
+    app
+}
+
+#[test]
+fn menu_opens_the_editor_on_an_empty_board() {
+    let mut app = App::new();
+    press(&mut app, "e");
+
+    assert!(!app.menu);
+    assert_eq!(app.editor.as_ref().unwrap().path, None);
+    assert_eq!(app.map_size(), (40, 30));
+    assert_eq!(app.game.board().bomb_cnt(), 0);
+    assert!(app
+        .game
+        .board()
+        .tiles()
+        .iter()
+        .all(|tile| !tile.is_covered()));
+
+    press(&mut app, "c");
+    let screen = render(&mut app, 84, 34);
+    assert!(screen[0].contains("Editor: 1 mines"));
+    assert!(screen[31].contains("t: test  s: save  p: play"));
+
+    press(&mut app, "q");
+    assert!(app.menu);
+    assert!(app.editor.is_none());
+}
+
+#[test]
+fn toggled_mines_update_the_numbers() {
+    let mut app = editor_app();
+    press(&mut app, "jc");
+
+    let board = app.game.board();
+    assert!(board.tile(1, 1).is_bomb());
+    assert!(!board.tile(1, 1).is_covered());
+    assert_eq!(board.tile(0, 0).content, TileContent::Empty(1));
+    assert_eq!(board.tile(1, 3).content, TileContent::Empty(0));
+
+    press(&mut app, "<Space>");
+    assert_eq!(app.game.board().bomb_cnt(), 0);
+    assert_eq!(app.game.board().tile(0, 0).content, TileContent::Empty(0));
+}
+
+#[test]
+fn the_last_safe_tile_keeps_its_place() {
+    let mut app = App::new();
+    app.open_editor(
+        Board::with_bombs(Grid::square(2, 2), [(0, 0), (1, 0), (0, 1)]),
+        None,
+    );
+    app.curr_pos = (1, 1);
+
+    press(&mut app, "c");
+    assert!(!app.game.board().tile(1, 1).is_bomb());
+    assert_eq!(app.game.board().bomb_cnt(), 3);
+}
+
+#[test]
+fn solver_checks_the_board() {
+    let mut app = editor_app();
+    press(&mut app, "jct");
+
+    let editor = app.editor.as_ref().unwrap();
+    assert_eq!(editor.check, Some(Check::Solvable((0, 3))));
+    assert_eq!(editor.status(), "no guessing from 0,3");
+
+    // a change forgets the check
+    press(&mut app, "c");
+    assert_eq!(app.editor.as_ref().unwrap().check, None);
+
+    // whatever is uncovered first, the two mines leave a guess
+    let mut app = App::new();
+    app.open_editor(Board::new(Grid::square(2, 3)), None);
+    app.curr_pos = (0, 0);
+    press(&mut app, "cjlct");
+    assert_eq!(
+        app.editor.as_ref().unwrap().check,
+        Some(Check::NeedsGuessing)
+    );
+    assert_eq!(app.editor.as_ref().unwrap().status(), "needs guessing");
+}
+
+#[test]
+fn saved_boards_load_in_the_game() {
+    let mut app = editor_app();
+    press(&mut app, "jcs");
+
+    let editor = app.editor.as_mut().unwrap();
+    assert!(editor.saved);
+    let text = editor.save.take().unwrap();
+    let board = board_file::from_text(&text).unwrap();
+    assert!(board.tile(1, 1).is_bomb());
+    assert_eq!(board.bomb_cnt(), 1);
+    assert_eq!(editor.status(), "saved");
+
+    press(&mut app, "c");
+    assert!(!app.editor.as_ref().unwrap().saved);
+}
+
+#[test]
+fn failed_saves_show_below_the_board() {
+    let mut app = App::new();
+    press(&mut app, "ecs");
+    // what the main loop does when the file cannot be written
+    app.editor.as_mut().unwrap().saved = false;
+    app.warning = Some("cannot save: Permission denied".to_string());
+
+    let screen = render(&mut app, 84, 34);
+    assert!(screen[31].contains("cannot save: Permission denied"));
+
+    press(&mut app, "l");
+    assert!(app.editor.is_some());
+    assert_eq!(app.warning, None);
+    let screen = render(&mut app, 84, 34);
+    assert!(screen[31].contains("t: test  s: save  p: play"));
+}
+
+#[test]
+fn play_starts_where_the_solver_found_no_guessing() {
+    let mut app = editor_app();
+    press(&mut app, "jctp");
+
+    assert!(app.editor.is_none());
+    assert!(app
+        .game
+        .board()
+        .tiles()
+        .iter()
+        .all(|tile| tile.is_covered()));
+    assert_eq!(app.curr_pos, (0, 3));
+
+    press(&mut app, "c");
+    assert!(!app.over());
+    assert!(!app.game.board().tile(2, 3).is_covered());
+}
+
+#[test]
+fn edit_option() {
+    let cli = Cli::parse(["--edit".to_string(), "mine.board".to_string()]).unwrap();
+    assert_eq!(cli.edit.unwrap().to_str(), Some("mine.board"));
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use common::{find_bomb, press, render_buffer};
+use minesweeper::{
+    app::App,
+    board_file,
+    effects::{self, Feedback, Sound},
+};
+use ratatui::style::Color;
+
+/// A column of mines walls off the last column, so a reveal on the left opens
+/// 30 tiles without winning.
+const WALLED: &str = "\
+minesweeper-board 1
+size 8 5
+......*.
+......*.
+......*.
+......*.
+......*.
+";
+
+fn walled_app() -> App {
+    let mut app = App::new();
+    app.load_board(board_file::from_text(WALLED).unwrap());
+    app
+}
+
+#[test]
+fn big_cascade_flashes_briefly() {
+    let mut app = walled_app();
+    press(&mut app, "c");
+    assert_eq!(app.effects.active, Some((Feedback::Cascade, 0)));
+    assert_eq!(
+        render_buffer(&mut app, 20, 8).get(1, 1).bg,
+        Color::LightYellow
+    );
+
+    app.tick();
+    assert_ne!(
+        render_buffer(&mut app, 20, 8).get(1, 1).bg,
+        Color::LightYellow
+    );
+    for _ in 0..3 {
+        app.tick();
+    }
+    assert_eq!(app.effects.active, None);
+}
+
+#[test]
+fn loss_flashes_red() {
+    let mut app = walled_app();
+    press(&mut app, "lllc");
+    assert!(app.game.is_over());
+    assert_eq!(app.effects.active, Some((Feedback::Loss, 0)));
+    assert_eq!(render_buffer(&mut app, 20, 8).get(1, 1).bg, Color::Red);
+}
+
+#[test]
+fn win_runs_colour_wave() {
+    let mut app = common::seeded_game(3, (9, 9), 10);
+    common::clear_board(&mut app);
+    assert_eq!(app.effects.active, Some((Feedback::Win, 0)));
+
+    let (bx, by) = find_bomb(&app);
+    let safe = if bx == 0 { (1, by) } else { (0, by) };
+    let cell = |app: &mut App| {
+        let buf = render_buffer(app, 30, 12);
+        buf.get(1 + safe.0 * 2, 1 + safe.1).bg
+    };
+    let first = cell(&mut app);
+    app.tick();
+    assert_ne!(cell(&mut app), first);
+}
+
+#[test]
+fn effects_can_be_turned_off() {
+    let mut app = walled_app();
+    app.effects.settings.set_visual(Feedback::Cascade, false);
+    press(&mut app, "c");
+    assert_eq!(app.effects.active, None);
+    assert_eq!(app.effects.sound, None);
+
+    app.effects.settings.sound = Sound::Bell;
+    press(&mut app, "lllc");
+    assert_eq!(app.effects.sound, Some(Feedback::Loss));
+}
+
+#[test]
+fn sound_cues() {
+    let cue = |sound, feedback| {
+        let mut out = vec![];
+        effects::play(sound, feedback, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    };
+
+    assert_eq!(cue(Sound::Off, Feedback::Win), "");
+    assert_eq!(cue(Sound::Bell, Feedback::Loss), "\x07");
+    assert_eq!(
+        cue(Sound::Osc, Feedback::Win),
+        "\x1b]9;minesweeper: board cleared\x07"
+    );
+}
@@ -0,0 +1,136 @@
+mod common;
+
+use common::{click, find_bomb, press, render, render_buffer, seeded_game};
+use crossterm::event::MouseButton;
+use minesweeper::{app::App, board_file, replay::Replay};
+use minesweeper_engine::{Status, TileCover};
+
+const BOARD: &str = "\
+minesweeper-board 1
+size 4 3
+*...
+....
+...*
+";
+
+fn board_app() -> App {
+    let mut app = App::new();
+    app.load_board(board_file::from_text(BOARD).unwrap());
+    app.curr_pos = (0, 0);
+    app
+}
+
+fn cover(app: &App, x: u16, y: u16) -> Option<TileCover> {
+    app.game.board().tile(x, y).cover
+}
+
+#[test]
+fn question_marks_can_be_skipped() {
+    let mut app = board_app();
+    press(&mut app, "ff");
+    assert_eq!(cover(&app, 0, 0), Some(TileCover::QuestionMark));
+
+    let mut app = board_app();
+    app.question_marks = false;
+    press(&mut app, "f");
+    assert_eq!(cover(&app, 0, 0), Some(TileCover::FlagMark(1)));
+    press(&mut app, "f");
+    assert_eq!(cover(&app, 0, 0), Some(TileCover::Empty));
+}
+
+#[test]
+fn replays_keep_their_question_marks() {
+    let mut app = App::new();
+    app.question_marks = false;
+    app.seed = Some(7);
+    app.init_mine_map((10, 8), 10);
+    assert!(!app.recording.as_ref().unwrap().question_marks);
+
+    // a changed preference does not change the game being played
+    app.question_marks = true;
+    app.curr_pos = (0, 0);
+    press(&mut app, "ff");
+    assert_eq!(cover(&app, 0, 0), Some(TileCover::Empty));
+
+    let text = app.recording.as_ref().unwrap().to_text();
+    assert!(text.contains("question-marks 0\n"));
+    assert!(!Replay::from_text(&text).unwrap().question_marks);
+    // replays from before the setting have question marks
+    let old = text.replace("question-marks 0\n", "");
+    assert!(Replay::from_text(&old).unwrap().question_marks);
+}
+
+#[test]
+fn flag_mode_flags_with_the_uncover_key() {
+    let mut app = board_app();
+    press(&mut app, "m");
+    assert!(app.flag_mode);
+
+    press(&mut app, "c");
+    assert_eq!(cover(&app, 0, 0), Some(TileCover::FlagMark(1)));
+    assert_eq!(app.game.status(), Status::Playing);
+
+    // uncovered numbers still chord
+    press(&mut app, "m");
+    press(&mut app, "lc");
+    press(&mut app, "m");
+    press(&mut app, "c");
+    assert!(!app.game.board().tile(1, 1).is_covered());
+
+    press(&mut app, "m");
+    assert!(!app.flag_mode);
+}
+
+#[test]
+fn flag_mode_shows_in_title() {
+    let mut app = seeded_game(7, (10, 8), 10);
+    press(&mut app, "m");
+    assert_eq!(render(&mut app, 24, 10)[0], "┌Game (flag mode)────┐");
+}
+
+#[test]
+fn mouse_clicks_act_on_the_clicked_tile() {
+    let mut app = board_app();
+    render_buffer(&mut app, 20, 6);
+
+    // tiles are two cells wide, inside the border
+    click(&mut app, MouseButton::Right, 1, 1);
+    assert_eq!(app.curr_pos, (0, 0));
+    assert_eq!(cover(&app, 0, 0), Some(TileCover::FlagMark(1)));
+
+    click(&mut app, MouseButton::Left, 6, 2);
+    assert_eq!(app.curr_pos, (2, 1));
+    assert!(!app.game.board().tile(2, 1).is_covered());
+
+    // the border and the space outside the board do nothing
+    click(&mut app, MouseButton::Left, 0, 0);
+    click(&mut app, MouseButton::Left, 15, 2);
+    assert_eq!(app.curr_pos, (2, 1));
+}
+
+#[test]
+fn mouse_follows_flag_mode() {
+    let mut app = board_app();
+    render_buffer(&mut app, 20, 6);
+    press(&mut app, "m");
+
+    click(&mut app, MouseButton::Left, 7, 3);
+    assert_eq!(cover(&app, 3, 2), Some(TileCover::FlagMark(1)));
+    assert_eq!(app.game.status(), Status::Playing);
+}
+
+#[test]
+fn mouse_is_ignored_outside_games() {
+    let mut app = seeded_game(7, (10, 8), 10);
+    app.curr_pos = find_bomb(&app);
+    press(&mut app, "c");
+    render_buffer(&mut app, 24, 10);
+
+    let pos = app.curr_pos;
+    click(&mut app, MouseButton::Left, 1, 1);
+    assert_eq!(app.curr_pos, pos);
+
+    let mut app = App::new();
+    click(&mut app, MouseButton::Left, 1, 1);
+    assert!(app.menu);
+}
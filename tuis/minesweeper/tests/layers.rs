@@ -0,0 +1,138 @@
+mod common;
+
+use common::{clear_board, press, render};
+use minesweeper::{app::App, board_file, cli::Cli, replay::Replay};
+use minesweeper_engine::{Status, TileContent};
+
+const BOARD: &str = "\
+minesweeper-board 1
+size 3 2
+layers 3
+*..
+...
+
+...
+...
+
+...
+..*
+";
+
+fn board_app() -> App {
+    let mut app = App::new();
+    app.load_board(board_file::from_text(BOARD).unwrap());
+    app
+}
+
+#[test]
+fn board_files_stack_layers() {
+    let board = board_file::from_text(BOARD).unwrap();
+
+    assert_eq!(board.size(), (3, 6));
+    assert_eq!(board.grid().layers, 3);
+    // the middle layer touches both mines, the corner away from both only one
+    assert_eq!(board.tile(1, 3).content, TileContent::Empty(2));
+    assert_eq!(board.tile(2, 2).content, TileContent::Empty(1));
+    assert_eq!(board.tile(2, 4).content, TileContent::Empty(1));
+    assert_eq!(board_file::to_text(&board), BOARD);
+
+    assert!(board_file::from_text(&BOARD.replace("layers 3", "layers 2")).is_err());
+    // layers ignore the shape, wrap and neighbourhood, so files can't ask for them
+    for line in ["shape hex", "wrap 1", "neighbours knight"] {
+        let text = BOARD.replace("layers 3", &format!("layers 3\n{}", line));
+        assert!(board_file::from_text(&text).is_err());
+    }
+}
+
+#[test]
+fn cursor_moves_within_and_between_layers() {
+    let mut app = board_app();
+    // the start is on the middle layer
+    assert_eq!(app.curr_pos, (0, 2));
+
+    press(&mut app, "kk");
+    assert_eq!(app.curr_pos, (0, 2));
+    press(&mut app, "G");
+    assert_eq!(app.curr_pos, (0, 3));
+    press(&mut app, "<PageUp>");
+    assert_eq!(app.curr_pos, (0, 1));
+    press(&mut app, "<PageUp>");
+    assert_eq!(app.curr_pos, (0, 1));
+    press(&mut app, "2<PageDown>");
+    assert_eq!(app.curr_pos, (0, 5));
+}
+
+#[test]
+fn layers_around_the_shown_one_are_ghosted() {
+    let mut app = board_app();
+    app.curr_pos = (0, 3);
+    press(&mut app, "c");
+
+    let screen = render(&mut app, 24, 6);
+    assert_eq!(
+        screen,
+        [
+            "┌Game (┐ ┌1──┐ ┌3──┐",
+            "│ㅁㅁㅁ│ │■■■│ │■■■│",
+            "│ 1ㅁㅁ│ │■■■│ │■■■│",
+            "└──────┘ └───┘ └───┘",
+            "",
+            "",
+        ]
+    );
+}
+
+#[test]
+fn layered_games_are_recorded() {
+    let mut app = App::new();
+    app.layers = 3;
+    app.seed = Some(5);
+    app.init_mine_map((4, 4), 6);
+    assert_eq!(app.map_size(), (4, 12));
+
+    let text = app.recording.as_ref().unwrap().to_text();
+    assert!(text.contains("layers 3\n"));
+    assert_eq!(Replay::from_text(&text).unwrap().layers, 3);
+
+    press(&mut app, "+");
+    assert!(render(&mut app, 60, 30)[0].contains("Game (layer 2/3)"));
+
+    clear_board(&mut app);
+    assert_eq!(app.game.status(), Status::Won);
+    assert_eq!(app.result.as_ref().unwrap().key, "4x4x3/6");
+}
+
+#[test]
+fn layered_replays_keep_the_cube_neighbours() {
+    let replay = |line: &str| {
+        Replay::from_text(&format!(
+            "minesweeper-replay 1\nsize 4 4\nmines 6\nlayers 3\n{}\n",
+            line
+        ))
+    };
+
+    assert!(replay("wrap 0").is_ok());
+    assert!(replay("wrap 1").is_err());
+    assert!(replay("shape hex").is_err());
+    assert!(replay("neighbours ring").is_err());
+}
+
+#[test]
+fn layers_option() {
+    let parse = |args: &str| Cli::parse(args.split_whitespace().map(String::from));
+
+    let cli = parse("--layers 3 -W 4 -H 4 -m 47").unwrap();
+    assert_eq!(cli.layers, Some(3));
+    assert_eq!(cli.board().unwrap(), Some(((4, 4), 47)));
+
+    assert!(parse("--layers 3 -W 4 -H 4 -m 48")
+        .unwrap()
+        .board()
+        .is_err());
+    assert!(parse("--layers 10").unwrap().board().is_err());
+    assert!(parse("--layers 2 --wrap").unwrap().board().is_err());
+    assert!(parse("--layers 2 --neighbours knight")
+        .unwrap()
+        .board()
+        .is_err());
+}
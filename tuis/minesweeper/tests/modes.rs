@@ -0,0 +1,156 @@
+mod common;
+
+use std::time::{Duration, Instant};
+
+use common::{find_bomb, press, render, render_buffer};
+use minesweeper::{
+    app::App,
+    board_file,
+    mode::{self, GameMode},
+    replay::Replay,
+};
+use minesweeper_engine::{Board, Status};
+use ratatui::style::Color;
+
+fn mode_game(mode: GameMode) -> App {
+    let mut app = App::new();
+    app.mode = mode;
+    app.lives = 2;
+    app.seed = Some(7);
+    app.init_mine_map((10, 8), 10);
+    app
+}
+
+/// Positions of every mine of a board.
+fn bombs(board: &Board) -> Vec<(u16, u16)> {
+    (0..board.tiles().len())
+        .filter(|&i| board.tiles()[i].is_bomb())
+        .map(|i| board.position(i))
+        .collect()
+}
+
+#[test]
+fn lives_mode_goes_on_after_a_mine() {
+    let mut app = mode_game(GameMode::Lives);
+    assert_eq!(app.game.lives(), 2);
+    let bombs = bombs(app.game.board());
+
+    app.curr_pos = bombs[0];
+    press(&mut app, "c");
+    assert_eq!(app.game.status(), Status::Playing);
+    assert!(!app.over());
+    assert_eq!(app.game.hits(), [bombs[0]]);
+    assert!(app.result.is_none());
+
+    let screen = render(&mut app, 24, 10);
+    assert_eq!(screen[9], "└lives 1─────────────┘");
+    let (x, y) = bombs[0];
+    let buffer = render_buffer(&mut app, 24, 10);
+    assert_eq!(buffer.get(1 + x * 2, 1 + y).symbol, " *");
+
+    app.curr_pos = bombs[1];
+    press(&mut app, "c");
+    assert_eq!(app.game.status(), Status::Lost);
+    assert_eq!(app.result.as_ref().unwrap().key, "10x8/10 lives2");
+}
+
+#[test]
+fn hit_mines_are_marked_red() {
+    let mut app = mode_game(GameMode::Lives);
+    let (x, y) = find_bomb(&app);
+    app.curr_pos = (x, y);
+    press(&mut app, "cl");
+    // after the flash
+    while app.effects.active.is_some() {
+        app.tick();
+    }
+
+    let buffer = render_buffer(&mut app, 24, 10);
+    assert_eq!(buffer.get(1 + x * 2, 1 + y).bg, Color::LightRed);
+}
+
+#[test]
+fn time_attack_runs_out() {
+    let mut app = mode_game(GameMode::TimeAttack);
+    // 70 safe tiles
+    assert_eq!(app.time_limit, Some(mode::time_limit(70)));
+    assert_eq!(mode::time_limit(70), Duration::from_secs(47));
+    assert!(render(&mut app, 24, 10)[9].starts_with("└4"));
+
+    app.tick();
+    assert_eq!(app.game.status(), Status::Playing);
+
+    app.started_at = Some(Instant::now() - Duration::from_secs(60));
+    app.tick();
+    assert_eq!(app.game.status(), Status::Lost);
+    assert!(app.over());
+    let result = app.result.as_ref().unwrap();
+    assert_eq!(result.key, "10x8/10 time-attack");
+    assert!(!result.won);
+}
+
+#[test]
+fn openings_add_time() {
+    let mut app = mode_game(GameMode::TimeAttack);
+    let board = app.game.board();
+    let zero = (0..board.tiles().len())
+        .map(|i| board.position(i))
+        .find(|&(x, y)| {
+            !board.tile(x, y).is_bomb()
+                && board
+                    .neighbours(x, y)
+                    .all(|(nx, ny)| !board.tile(nx, ny).is_bomb())
+        })
+        .unwrap();
+    let before = app.time_limit.unwrap();
+
+    app.curr_pos = zero;
+    press(&mut app, "c");
+    assert_eq!(app.time_limit, Some(before + mode::OPENING_BONUS));
+}
+
+#[test]
+fn chords_add_time_for_the_openings_they_uncover() {
+    let mut app = App::new();
+    app.load_board(board_file::from_text("minesweeper-board 1\nsize 5 2\n..*..\n.....\n").unwrap());
+    let start = Duration::from_secs(30);
+    app.time_limit = Some(start);
+
+    // a 1 next to the mine is no opening
+    app.curr_pos = (1, 1);
+    press(&mut app, "c");
+    assert_eq!(app.time_limit, Some(start));
+
+    // chording it uncovers the zeros on the left
+    app.curr_pos = (2, 0);
+    press(&mut app, "f");
+    app.curr_pos = (1, 1);
+    press(&mut app, "c");
+    assert_eq!(app.time_limit, Some(start + mode::OPENING_BONUS));
+}
+
+#[test]
+fn menu_switches_mode() {
+    let mut app = App::new();
+    press(&mut app, "m");
+    assert_eq!(app.mode, GameMode::Lives);
+    assert_eq!(
+        render(&mut app, 32, 12)[0],
+        "┌Menu: lives─────────────────┐"
+    );
+
+    press(&mut app, "mm");
+    assert_eq!(app.mode, GameMode::Classic);
+}
+
+#[test]
+fn replays_keep_the_mode() {
+    let app = mode_game(GameMode::Lives);
+    let replay = app.recording.as_ref().unwrap();
+    assert_eq!(replay.mode, GameMode::Lives);
+
+    let text = replay.to_text();
+    assert!(text.contains("mode lives\nlives 2\n"));
+    let parsed = Replay::from_text(&text).unwrap();
+    assert_eq!((parsed.mode, parsed.lives), (GameMode::Lives, 2));
+}
@@ -0,0 +1,176 @@
+mod common;
+
+use common::{press, seeded_game};
+use minesweeper::app::App;
+use minesweeper_engine::Shape;
+
+fn game() -> App {
+    let mut app = seeded_game(5, (20, 15), 35);
+    app.curr_pos = (0, 0);
+    app
+}
+
+#[test]
+fn single_steps_wrap_around() {
+    let mut app = game();
+
+    press(&mut app, "h");
+    assert_eq!(app.curr_pos, (19, 0));
+    press(&mut app, "k");
+    assert_eq!(app.curr_pos, (19, 14));
+    press(&mut app, "lj");
+    assert_eq!(app.curr_pos, (0, 0));
+}
+
+#[test]
+fn count_prefix_repeats_movement() {
+    let mut app = game();
+
+    press(&mut app, "5l");
+    assert_eq!(app.curr_pos, (5, 0));
+    press(&mut app, "12j");
+    assert_eq!(app.curr_pos, (5, 12));
+    press(&mut app, "10<Left>");
+    assert_eq!(app.curr_pos, (15, 12));
+}
+
+#[test]
+fn count_prefix_only_applies_once() {
+    let mut app = game();
+
+    press(&mut app, "3ll");
+    assert_eq!(app.curr_pos, (4, 0));
+    assert_eq!(app.move_count, None);
+}
+
+#[test]
+fn edge_jumps() {
+    let mut app = game();
+    app.curr_pos = (7, 7);
+
+    press(&mut app, "$");
+    assert_eq!(app.curr_pos, (19, 7));
+    press(&mut app, "0");
+    assert_eq!(app.curr_pos, (0, 7));
+    press(&mut app, "G");
+    assert_eq!(app.curr_pos, (0, 14));
+    press(&mut app, "g");
+    assert_eq!(app.curr_pos, (0, 0));
+    press(&mut app, "4G");
+    assert_eq!(app.curr_pos, (0, 3));
+}
+
+#[test]
+fn zero_continues_a_count() {
+    let mut app = game();
+
+    press(&mut app, "10l");
+    assert_eq!(app.curr_pos, (10, 0));
+}
+
+#[test]
+fn jump_to_covered_skips_uncovered_and_flagged_tiles() {
+    let mut app = game();
+    let covered_in_row = |app: &App, y: u16| -> Vec<u16> {
+        (0..20)
+            .filter(|&x| app.game.board().tile(x, y).is_covered())
+            .collect()
+    };
+
+    // flag the first covered tile right of the cursor, the jump has to skip it
+    let first = covered_in_row(&app, 0)[1];
+    app.curr_pos = (first, 0);
+    press(&mut app, "f");
+    app.curr_pos = (0, 0);
+
+    press(&mut app, "L");
+    let expected = covered_in_row(&app, 0)
+        .into_iter()
+        .find(|&x| x > 0 && !app.game.board().tile(x, 0).is_flagged())
+        .unwrap();
+    assert_eq!(app.curr_pos, (expected, 0));
+}
+
+#[test]
+fn count_prefix_repeats_jumps() {
+    let mut app = game();
+
+    // every tile is covered, so each jump moves one tile
+    press(&mut app, "3L");
+    assert_eq!(app.curr_pos, (3, 0));
+    press(&mut app, "2J");
+    assert_eq!(app.curr_pos, (3, 2));
+    // a count past the edge stops at the last covered tile
+    press(&mut app, "30L");
+    assert_eq!(app.curr_pos, (19, 2));
+
+    let mut app = App::new();
+    app.no_guess = true;
+    app.seed = Some(3);
+    app.init_mine_map((12, 8), 12);
+    let mut twice = App::new();
+    twice.no_guess = true;
+    twice.seed = Some(3);
+    twice.init_mine_map((12, 8), 12);
+
+    press(&mut app, "2n");
+    press(&mut twice, "nn");
+    assert_eq!(app.curr_pos, twice.curr_pos);
+}
+
+#[test]
+fn jump_to_frontier() {
+    let mut app = App::new();
+    app.no_guess = true;
+    app.seed = Some(3);
+    app.init_mine_map((12, 8), 12);
+
+    press(&mut app, "n");
+    let (x, y) = app.curr_pos;
+    let board = app.game.board();
+    assert!(board.tile(x, y).is_covered());
+    assert!(board
+        .neighbours(x, y)
+        .any(|(nx, ny)| !board.tile(nx, ny).is_covered()));
+
+    let first = app.curr_pos;
+    press(&mut app, "nN");
+    assert_eq!(app.curr_pos, first);
+}
+
+#[test]
+fn frontier_jump_without_frontier_stays() {
+    let mut app = game();
+    app.curr_pos = (3, 3);
+
+    press(&mut app, "n");
+    assert_eq!(app.curr_pos, (3, 3));
+}
+
+#[test]
+fn hex_keys_follow_row_offsets() {
+    let mut app = App::new();
+    app.shape = Shape::Hex;
+    app.seed = Some(5);
+    app.init_mine_map((10, 8), 10);
+    app.curr_pos = (4, 4);
+
+    press(&mut app, "e");
+    assert_eq!(app.curr_pos, (4, 3));
+    press(&mut app, "e");
+    assert_eq!(app.curr_pos, (5, 2));
+    press(&mut app, "2z");
+    assert_eq!(app.curr_pos, (4, 4));
+    press(&mut app, "wad");
+    assert_eq!(app.curr_pos, (3, 3));
+    press(&mut app, "x9a");
+    assert_eq!(app.curr_pos, (0, 4));
+}
+
+#[test]
+fn hex_keys_do_nothing_on_square_boards() {
+    let mut app = game();
+
+    press(&mut app, "wedzx");
+    assert_eq!(app.curr_pos, (0, 0));
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use common::clear_board;
+use minesweeper::{app::App, board_file, cli::Cli, replay::Replay};
+use minesweeper_engine::{Neighbourhood, Status, TileContent};
+
+const KNIGHT_BOARD: &str = "\
+minesweeper-board 1
+size 4 3
+neighbours knight
+*...
+....
+....
+";
+
+fn knight_game() -> App {
+    let mut app = App::new();
+    app.neighbourhood = Neighbourhood::Knight;
+    app.seed = Some(3);
+    app.init_mine_map((8, 6), 6);
+    app
+}
+
+#[test]
+fn board_files_keep_the_neighbourhood() {
+    let board = board_file::from_text(KNIGHT_BOARD).unwrap();
+
+    assert_eq!(board.grid().neighbourhood, Neighbourhood::Knight);
+    // a knight's move away from the mine
+    assert_eq!(board.tile(1, 2).content, TileContent::Empty(1));
+    assert_eq!(board.tile(1, 1).content, TileContent::Empty(0));
+    assert_eq!(board_file::to_text(&board), KNIGHT_BOARD);
+
+    let custom = KNIGHT_BOARD.replace("knight", "0,2;2,0");
+    let board = board_file::from_text(&custom).unwrap();
+    assert_eq!(board.tile(2, 0).content, TileContent::Empty(1));
+    assert_eq!(board.tile(1, 0).content, TileContent::Empty(0));
+
+    assert!(board_file::from_text(&KNIGHT_BOARD.replace("knight", "king")).is_err());
+}
+
+#[test]
+fn knight_games_are_recorded() {
+    let mut app = knight_game();
+    assert_eq!(app.game.board().grid().neighbourhood, Neighbourhood::Knight);
+
+    let text = app.recording.as_ref().unwrap().to_text();
+    assert!(text.contains("neighbours knight\n"));
+    let parsed = Replay::from_text(&text).unwrap();
+    assert_eq!(parsed.neighbourhood, Neighbourhood::Knight);
+
+    clear_board(&mut app);
+    assert_eq!(app.game.status(), Status::Won);
+    assert_eq!(app.result.as_ref().unwrap().key, "8x6/6 knight");
+}
+
+#[test]
+fn neighbours_option() {
+    let parse = |args: &str| Cli::parse(args.split_whitespace().map(String::from));
+
+    let cli = parse("--neighbours ring").unwrap();
+    assert_eq!(cli.neighbourhood, Some(Neighbourhood::Ring));
+    let cli = parse("--neighbours=1,2;-2,1").unwrap();
+    assert_eq!(cli.neighbourhood.unwrap().name(), "1,2;-2,1");
+
+    assert!(parse("--neighbours king").is_err());
+    assert!(parse("--neighbours 0,0").is_err());
+    assert!(parse("--neighbours knight --shape hex")
+        .unwrap()
+        .board()
+        .is_err());
+}
@@ -0,0 +1,159 @@
+mod common;
+
+use common::{press, render};
+use minesweeper::{
+    app::App,
+    puzzle::{Goal, PuzzlePack},
+};
+use minesweeper_engine::{
+    solver::{deduce, Knowledge},
+    GameState, Status, TileContent,
+};
+
+const PACK: &str = "\
+minesweeper-puzzles 1
+# two small puzzles
+
+puzzle Find it
+size 4 3
+oooo
+oooo
+F.!*
+
+puzzle Clear it
+size 3 2
+ooo
+.*.
+";
+
+fn app_with_pack() -> App {
+    let mut app = App::new();
+    app.puzzle_pack = PuzzlePack::from_text("test", PACK).unwrap();
+    app
+}
+
+#[test]
+fn pack_is_parsed() {
+    let pack = PuzzlePack::from_text("test", PACK).unwrap();
+
+    assert_eq!(pack.puzzles.len(), 2);
+    let find = &pack.puzzles[0];
+    assert_eq!(find.name, "Find it");
+    assert_eq!(find.goal, Goal::Find(2, 2));
+    assert!(!find.board.tile(1, 1).is_covered());
+    assert!(find.board.tile(0, 2).is_flagged());
+    assert_eq!(find.board.tile(1, 1).content, TileContent::Empty(1));
+    assert_eq!(pack.puzzles[1].goal, Goal::Clear);
+}
+
+#[test]
+fn malformed_packs_are_rejected() {
+    let reject = |text: &str| PuzzlePack::from_text("bad", text).is_err();
+
+    assert!(reject("puzzle A\nsize 2 2\no.\n.*\n"));
+    assert!(reject("minesweeper-puzzles 1\n"));
+    assert!(reject(
+        "minesweeper-puzzles 1\nsize 2 2\npuzzle A\nsize 2 2\no.\n.*\n"
+    ));
+    assert!(reject(
+        "minesweeper-puzzles 1\npuzzle A\nsize 2 2\n!!\n.*\n"
+    ));
+    assert!(reject(
+        "minesweeper-puzzles 1\npuzzle A\nsize 2 2\noo\no*\n"
+    ));
+    assert!(reject(
+        "minesweeper-puzzles 1\npuzzle A\nsize 2 2\no.\n.x\n"
+    ));
+}
+
+/// Plays a puzzle with the solver alone, without ever guessing.
+fn solve_by_deduction(game: &mut GameState, goal: Goal) -> bool {
+    loop {
+        let solved = match goal {
+            Goal::Clear => game.status() == Status::Won,
+            Goal::Find(x, y) => !game.board().tile(x, y).is_covered(),
+        };
+        if solved {
+            return true;
+        }
+
+        let view: Vec<Knowledge> = game.board().tiles().iter().map(Knowledge::of).collect();
+        let deduction = deduce(game.board().grid(), &view);
+        if deduction.is_empty() {
+            return false;
+        }
+        for idx in deduction.safe {
+            let (x, y) = game.board().position(idx);
+            game.reveal(x, y);
+        }
+        for idx in deduction.mines {
+            let (x, y) = game.board().position(idx);
+            if !game.board().tile(x, y).is_flagged() {
+                game.toggle_flag(x, y, true);
+            }
+        }
+    }
+}
+
+#[test]
+fn builtin_puzzles_need_no_guessing() {
+    let pack = PuzzlePack::builtin();
+    assert!(!pack.puzzles.is_empty());
+    for puzzle in &pack.puzzles {
+        let mut game = GameState::new(puzzle.board.clone());
+        assert!(
+            solve_by_deduction(&mut game, puzzle.goal),
+            "puzzle `{}` needs a guess",
+            puzzle.name
+        );
+    }
+}
+
+#[test]
+fn finding_the_tile_solves_the_puzzle() {
+    let mut app = app_with_pack();
+    press(&mut app, "p");
+    assert_eq!(app.puzzle_select, Some(0));
+    press(&mut app, "<Enter>");
+    assert!(app.puzzle.is_some());
+    assert!(!app.over());
+
+    // cursor starts at (1, 0)
+    press(&mut app, "jjlc");
+    assert!(app.over());
+    assert!(app.puzzle.is_some_and(|run| run.solved));
+    assert!(app.puzzle_progress.unsaved);
+    assert!(render(&mut app, 20, 6).join("\n").contains("SOLVED!"));
+
+    // back to the list, on the next unsolved puzzle
+    press(&mut app, "<Enter>");
+    assert_eq!(app.puzzle_select, Some(1));
+    let screen = render(&mut app, 36, 8).join("\n");
+    assert!(screen.contains("✓  1. Find it"));
+    assert!(screen.contains("solved 1/2"));
+}
+
+#[test]
+fn clear_puzzle_needs_every_safe_tile() {
+    let mut app = app_with_pack();
+    app.start_puzzle(1);
+
+    // cursor starts at (0, 0)
+    press(&mut app, "jc");
+    assert!(!app.over());
+    press(&mut app, "llc");
+    assert!(app.over());
+    assert_eq!(app.game.status(), Status::Won);
+    assert!(app.puzzle.is_some_and(|run| run.solved));
+}
+
+#[test]
+fn mine_fails_the_puzzle() {
+    let mut app = app_with_pack();
+    app.start_puzzle(0);
+
+    press(&mut app, "jjllc");
+    assert!(app.over());
+    assert!(app.puzzle.is_some_and(|run| !run.solved));
+    assert!(!app.puzzle_progress.unsaved);
+}
@@ -0,0 +1,201 @@
+mod common;
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    thread,
+    time::{Duration, Instant},
+};
+
+use common::{clear_board, find_bomb, press};
+use minesweeper::{
+    app::App,
+    net::{Message, Mode, Peer, PROTOCOL_VERSION},
+    race::Winner,
+    replay::Replay,
+};
+use minesweeper_engine::Shape;
+
+/// A host and a guest app racing each other over localhost.
+fn race() -> (App, App) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let guest = thread::spawn(move || {
+        let mut app = App::new();
+        app.join_race(Peer::connect(addr, Mode::Race).unwrap())
+            .unwrap();
+        app
+    });
+
+    let mut host = App::new();
+    host.seed = Some(9);
+    let peer = Peer::accept(&listener, Mode::Race).unwrap();
+    host.host_race(peer, (12, 10), 15).unwrap();
+    (host, guest.join().unwrap())
+}
+
+/// Ticks `app` until `done` holds, failing after a second.
+fn tick_until(app: &mut App, done: impl Fn(&App) -> bool) {
+    let start = Instant::now();
+    while !done(app) {
+        assert!(start.elapsed() < Duration::from_secs(1), "timed out");
+        thread::sleep(Duration::from_millis(5));
+        app.tick();
+    }
+}
+
+#[test]
+fn messages_round_trip() {
+    let mut setup = Replay::new((30, 16), 99, 42, false);
+    setup.shape = Shape::Hex;
+    setup.max_mines = 2;
+    let messages = [
+        Message::Hello {
+            version: PROTOCOL_VERSION,
+            mode: Mode::Race,
+        },
+        Message::Board(setup),
+        Message::Progress {
+            revealed: 12,
+            total: 381,
+        },
+        Message::Finished {
+            won: true,
+            ms: 61234,
+        },
+        Message::Bye,
+    ];
+
+    for message in messages {
+        assert_eq!(Message::from_line(&message.to_line()).unwrap(), message);
+    }
+    assert!(Message::from_line("progress lots").is_err());
+    assert!(Message::from_line("finished maybe 10").is_err());
+    assert!(Message::from_line("progress 12 11").is_err());
+}
+
+#[test]
+fn unplayable_boards_are_rejected() {
+    let board = |fields: &str| {
+        Message::from_line(&format!("board {} 1 0 square 0 1 standard 1 0 1", fields))
+    };
+
+    assert!(board("12 10 15").is_ok());
+    assert!(board("0 10 15").is_err());
+    assert!(board("12 10 120").is_err());
+    assert!(board("12 10 0").is_err());
+    assert!(Message::from_line("board 12 10 15 1 0 square 0 10 standard 1 0 1").is_err());
+    assert!(Message::from_line("board 12 10 15 1 0 square 0 1 standard 0 0 1").is_err());
+
+    // a host sending one is left
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let host = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        writeln!(stream, "minesweeper-net {} race", PROTOCOL_VERSION).unwrap();
+        writeln!(stream, "board 0 10 15 1 0 square 0 1 standard 1 0 1").unwrap();
+        let mut line = String::new();
+        let mut reader = BufReader::new(stream);
+        while reader.read_line(&mut line).unwrap() > 0 {}
+    });
+
+    let error = App::new()
+        .join_race(Peer::connect(addr, Mode::Race).unwrap())
+        .unwrap_err();
+    assert!(error.to_string().contains("playable board"));
+    host.join().unwrap();
+}
+
+#[test]
+fn both_sides_get_the_same_board() {
+    let (host, guest) = race();
+
+    assert_eq!(guest.map_size(), (12, 10));
+    assert_eq!(host.game.board().tiles(), guest.game.board().tiles());
+}
+
+#[test]
+fn progress_reaches_the_opponent() {
+    let (mut host, mut guest) = race();
+
+    press(&mut host, "c");
+    let revealed = 12 * 10 - 15 - host.game.empty_cnt();
+    tick_until(&mut guest, |app| app.race.as_ref().unwrap().opponent.0 > 0);
+
+    let race = guest.race.as_ref().unwrap();
+    assert_eq!(race.opponent, (revealed, 12 * 10 - 15));
+    assert!(!guest.over());
+
+    // the bar below the board
+    let percent = revealed * 100 / (12 * 10 - 15);
+    let screen = common::render(&mut guest, 26, 14);
+    assert_eq!(screen[11], "└────────────────────────┘");
+    assert!(screen[12].starts_with(&format!("opponent {:>3}% ", percent)));
+    assert!(screen[12].ends_with('░'));
+}
+
+#[test]
+fn progress_bar_stays_full_at_most() {
+    let (mut host, _guest) = race();
+    host.race.as_mut().unwrap().opponent = (u32::MAX, 105);
+
+    assert_eq!(host.race.as_ref().unwrap().opponent_percent(), 100);
+    let screen = common::render(&mut host, 26, 14);
+    assert!(screen[12].starts_with("opponent 100% █"));
+    assert!(!screen[12].contains('░'));
+}
+
+#[test]
+fn first_to_clear_wins() {
+    let (mut host, mut guest) = race();
+
+    clear_board(&mut guest);
+    assert_eq!(guest.race_winner(), Some(Winner::Me));
+
+    tick_until(&mut host, App::over);
+    assert_eq!(host.race_winner(), Some(Winner::Opponent));
+    assert!(!host.game.is_over());
+}
+
+#[test]
+fn hitting_a_mine_hands_over_the_win() {
+    let (mut host, mut guest) = race();
+
+    host.curr_pos = find_bomb(&host);
+    press(&mut host, "c");
+    assert_eq!(host.race_winner(), Some(Winner::Opponent));
+
+    tick_until(&mut guest, App::over);
+    assert_eq!(guest.race_winner(), Some(Winner::Me));
+}
+
+#[test]
+fn leaving_forfeits_the_race() {
+    let (mut host, guest) = race();
+    drop(guest);
+
+    tick_until(&mut host, App::over);
+    let race = host.race.as_ref().unwrap();
+    assert!(race.opponent_left);
+    assert_eq!(race.winner, Some(Winner::Me));
+}
+
+#[test]
+fn other_protocol_versions_are_rejected() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let stranger = thread::spawn(move || {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        writeln!(stream, "minesweeper-net {} race", PROTOCOL_VERSION + 1).unwrap();
+        let mut line = String::new();
+        BufReader::new(stream).read_line(&mut line).unwrap();
+        line
+    });
+
+    let error = Peer::accept(&listener, Mode::Race).unwrap_err();
+    assert!(error.to_string().contains("protocol version"));
+    assert!(stranger
+        .join()
+        .unwrap()
+        .starts_with(&format!("minesweeper-net {} race", PROTOCOL_VERSION)));
+}
@@ -0,0 +1,163 @@
+mod common;
+
+use common::{find_bomb, press, render, seeded_game};
+use minesweeper::{
+    app::{App, MenuKind},
+    effects::Sound,
+    settings::{Field, Settings},
+    theme::{Glyphs, Theme},
+};
+use minesweeper_engine::Status;
+
+#[test]
+fn text_round_trip() {
+    let mut settings = Settings {
+        glyphs: Glyphs::Ascii,
+        confirm_quit: true,
+        ..Default::default()
+    };
+    settings.effects.sound = Sound::Bell;
+    settings.effects.loss_flash = false;
+
+    let text = settings.to_text();
+    assert!(text.contains("glyphs = ascii\n"));
+    assert!(text.contains("loss-flash = off\n"));
+    assert_eq!(Settings::from_text(&text), (settings, vec![]));
+}
+
+#[test]
+fn missing_keys_keep_defaults() {
+    let (settings, warnings) = Settings::from_text("# mine\n\nconfirm-quit = on\n");
+    assert!(settings.confirm_quit);
+    assert_eq!(settings.theme, Theme::default());
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn invalid_lines_are_skipped_with_a_warning() {
+    let (settings, warnings) = Settings::from_text(
+        "theme = dark\nvolume = 11\nconfirm-quit = yes\nglyphs ascii\nquestion-marks = off\n",
+    );
+
+    // the good lines still count, the bad ones keep their default
+    assert_eq!(settings.theme, Theme::Dark);
+    assert!(!settings.question_marks);
+    assert!(!settings.confirm_quit);
+    assert_eq!(settings.glyphs, Glyphs::default());
+    assert_eq!(
+        warnings,
+        [
+            "settings.conf:2: unknown setting `volume`",
+            "settings.conf:3: invalid value `yes` for confirm-quit",
+            "settings.conf:4: expected `key = value`",
+        ]
+    );
+}
+
+#[test]
+fn warnings_show_below_the_menu_until_a_key() {
+    let mut app = App::new();
+    app.warning = Some("settings.conf:2: unknown setting `volume`".to_string());
+
+    let screen = render(&mut app, 50, 14);
+    assert_eq!(screen[12], "settings.conf:2: unknown setting `volume`");
+
+    press(&mut app, "j");
+    assert_eq!(render(&mut app, 50, 14)[12], "");
+}
+
+#[test]
+fn screen_changes_settings() {
+    let mut app = App::new();
+    press(&mut app, "s");
+    assert_eq!(app.settings_select, Some(0));
+
+    // glyphs, then confirm quit
+    press(&mut app, "jljjl");
+    assert_eq!(app.settings.glyphs, Glyphs::Ascii);
+    assert_eq!(app.glyphs, Glyphs::Ascii);
+    assert!(app.confirm_quit);
+    assert!(app.settings_unsaved);
+
+    let screen = render(&mut app, 40, 16).join("\n");
+    assert!(screen.contains("Glyphs                   ascii"));
+    assert!(screen.contains("Confirm quit                on"));
+
+    // the last row puts everything back
+    press(&mut app, "jjjjjj<Enter>");
+    assert_eq!(app.settings, Settings::default());
+    assert_eq!(app.glyphs, Glyphs::Unicode);
+
+    press(&mut app, "<Esc>");
+    assert_eq!(app.settings_select, None);
+    assert!(app.menu);
+}
+
+#[test]
+fn menu_focus_reaches_settings() {
+    let mut app = App::new();
+    press(&mut app, "ll");
+    assert!(matches!(app.menu_focus, MenuKind::Settings));
+
+    press(&mut app, "<Enter>");
+    assert_eq!(app.settings_select, Some(0));
+    assert!(app.menu);
+}
+
+#[test]
+fn quitting_asks_first() {
+    let mut app = seeded_game(7, (10, 8), 10);
+    app.confirm_quit = true;
+
+    press(&mut app, "q");
+    assert!(app.quitting);
+    assert!(!app.should_quit);
+    assert!(render(&mut app, 24, 10).join("\n").contains("QUIT? (y/n)"));
+
+    press(&mut app, "n");
+    assert!(!app.quitting);
+    assert!(!app.should_quit);
+
+    press(&mut app, "qy");
+    assert!(app.should_quit);
+}
+
+#[test]
+fn first_click_is_safe() {
+    let mut app = App::new();
+    app.safe_first_click = true;
+    app.seed = Some(7);
+    app.init_mine_map((10, 8), 10);
+    let (x, y) = find_bomb(&app);
+    app.curr_pos = (x, y);
+
+    press(&mut app, "c");
+    let board = app.game.board();
+    assert_eq!(app.game.status(), Status::Playing);
+    assert!(!board.tile(x, y).is_bomb());
+    assert_eq!(board.bomb_cnt(), 10);
+    assert!(app.recording.as_ref().unwrap().safe_first);
+
+    // later mines still explode
+    app.curr_pos = find_bomb(&app);
+    press(&mut app, "c");
+    assert_eq!(app.game.status(), Status::Lost);
+}
+
+#[test]
+fn ascii_glyphs() {
+    let mut app = seeded_game(7, (10, 8), 10);
+    app.glyphs = Glyphs::Ascii;
+    app.curr_pos = (0, 0);
+    press(&mut app, "f");
+
+    let screen = render(&mut app, 24, 10);
+    assert_eq!(screen[1], "│ F # # # # # # # # #│");
+}
+
+#[test]
+fn field_keys_are_unique() {
+    for field in Field::ALL {
+        assert_eq!(Field::from_key(field.key()), Some(field));
+    }
+}
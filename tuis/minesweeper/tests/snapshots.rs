@@ -0,0 +1,299 @@
+mod common;
+
+use common::{assert_screen, clear_board, find_bomb, press, render_buffer, seeded_game};
+use minesweeper::app::{App, MenuKind};
+use minesweeper_engine::{Board, GameState, Grid, Shape};
+use ratatui::style::Color;
+
+const MENU: [&str; 12] = [
+    "┌Menu────────────────────────┐",
+    "│┌Map Size────┐┌Game Level──┐│",
+    "││    LARGE   ││    HARD    ││",
+    "││   NORMAL   ││            ││",
+    "││    SMALL   ││   NORMAL   ││",
+    "││   EXPERT   ││            ││",
+    "││INTERMEDIATE││    EASY    ││",
+    "││  BEGINNER  ││            ││",
+    "│└────────────┘└────────────┘│",
+    "│          Settings          │",
+    "│    d: daily  p: puzzles    │",
+    "└────────────────────────────┘",
+];
+
+/// A 12x8 no-guess board that starts with an opening at (5, 3).
+fn opened_game() -> App {
+    let mut app = App::new();
+    app.no_guess = true;
+    app.seed = Some(3);
+    app.init_mine_map((12, 8), 12);
+    app
+}
+
+#[test]
+fn menu_screen() {
+    let mut app = App::new();
+
+    assert_screen(&mut app, 32, 12, &MENU);
+}
+
+#[test]
+fn menu_highlights_selection() {
+    let mut app = App::new();
+    press(&mut app, "jl");
+
+    assert!(matches!(app.menu_focus, MenuKind::GameLevel));
+    let buffer = render_buffer(&mut app, 32, 12);
+    // "SMALL" in the map size column, "NORMAL" in the game level column
+    assert_eq!(buffer.get(6, 4).bg, Color::Black);
+    assert_eq!(buffer.get(6, 3).bg, Color::White);
+    assert_eq!(buffer.get(20, 4).bg, Color::Black);
+    assert_screen(&mut app, 32, 12, &MENU);
+}
+
+#[test]
+fn classic_sizes_grey_out_the_level() {
+    let mut app = App::new();
+    press(&mut app, "jj");
+
+    let buffer = render_buffer(&mut app, 32, 12);
+    // "EXPERT" is picked, no level is and the level column is greyed out
+    assert_eq!(buffer.get(6, 5).bg, Color::Black);
+    assert_eq!(buffer.get(20, 4).bg, Color::White);
+    assert_eq!(buffer.get(20, 4).fg, Color::Gray);
+
+    // the focus skips the level, both ways
+    press(&mut app, "l");
+    assert!(matches!(app.menu_focus, MenuKind::Settings));
+    press(&mut app, "h");
+    assert!(matches!(app.menu_focus, MenuKind::MapSize));
+
+    press(&mut app, "<Enter>");
+    assert_eq!(app.game.board().bomb_cnt(), 99);
+}
+
+#[test]
+fn menu_opens_daily_calendar() {
+    let mut app = App::new();
+    press(&mut app, "d");
+    assert!(app.calendar.is_some());
+
+    press(&mut app, "<Esc>");
+    assert!(app.calendar.is_none());
+    assert!(!app.should_quit);
+    assert_screen(&mut app, 32, 12, &MENU);
+}
+
+#[test]
+fn menu_starts_selected_game() {
+    let mut app = App::new();
+    press(&mut app, "j<Enter>");
+
+    assert!(!app.menu);
+    assert_eq!(app.map_size(), (20, 15));
+    // 15.6% of the tiles
+    assert_eq!(app.game.board().bomb_cnt(), 47);
+}
+
+#[test]
+fn mid_game_board() {
+    let mut app = opened_game();
+    press(&mut app, "4lkf");
+
+    assert_eq!(app.curr_pos, (9, 2));
+    assert_screen(
+        &mut app,
+        28,
+        10,
+        &[
+            "┌Game────────────────────┐",
+            "│ㅁ 1 . . 2ㅁ 2 . . . . .│",
+            "│ㅁ 1 . . 2ㅁ 2 . 1 1 1 .│",
+            "│ㅁ 1 1 . 1 1 1 . 1 ⚑ 1 .│",
+            "│ㅁㅁ 1 . . . . . 1 1 1 .│",
+            "│ㅁㅁ 2 1 2 1 2 1 1 . . .│",
+            "│ㅁㅁㅁㅁㅁㅁㅁㅁ 3 1 1 .│",
+            "│ㅁㅁㅁㅁㅁㅁㅁㅁㅁㅁ 1 .│",
+            "│ㅁㅁㅁㅁㅁㅁㅁㅁㅁㅁ 1 .│",
+            "└────────────────────────┘",
+        ],
+    );
+
+    // the cursor swaps the colours of its tile
+    let buffer = render_buffer(&mut app, 28, 10);
+    let cursor = buffer.get(1 + 9 * 2, 3);
+    assert_eq!(cursor.bg, Color::Red);
+    assert_eq!(cursor.fg, Color::Rgb(200, 180, 180));
+}
+
+#[test]
+fn count_prefix_shows_in_title() {
+    let mut app = opened_game();
+    press(&mut app, "12");
+
+    let screen = common::render(&mut app, 28, 10);
+    assert_eq!(screen[0], "┌Game [12]───────────────┐");
+
+    press(&mut app, "h");
+    let screen = common::render(&mut app, 28, 10);
+    assert_eq!(screen[0], "┌Game────────────────────┐");
+}
+
+#[test]
+fn win_overlay() {
+    let mut app = seeded_game(7, (10, 8), 10);
+    clear_board(&mut app);
+
+    assert!(app.over());
+    assert_screen(
+        &mut app,
+        24,
+        10,
+        &[
+            "┌Game────────────────┐",
+            "│ 1 1 . . . . 1 1 1 .│",
+            "│ㅁ 1 . . 1 1 3ㅁ 3 1│",
+            "│ 1 2┌──────────┐   1│",
+            "│ . 1│ YOU WIN! │   2│",
+            "│ . 1└──────────┘   1│",
+            "│ 1 1 . . . . 1 2 2 1│",
+            "│ㅁ 1 . . . . . 1 1 1│",
+            "│ 1 1 . . . . . 1ㅁ 1│",
+            "└────────────────────┘",
+        ],
+    );
+
+    press(&mut app, "<Enter>");
+    assert_screen(&mut app, 32, 12, &MENU);
+}
+
+#[test]
+fn loss_overlay() {
+    let mut app = seeded_game(7, (10, 8), 10);
+    app.curr_pos = find_bomb(&app);
+    press(&mut app, "c");
+
+    assert!(app.over());
+    assert_screen(
+        &mut app,
+        24,
+        10,
+        &[
+            "┌Game────────────────┐",
+            "│ㅁㅁㅁㅁㅁㅁㅁㅁㅁㅁ│",
+            "│ *ㅁㅁㅁㅁㅁㅁ *ㅁㅁ│",
+            "│ㅁ ┌────────────┐ ㅁ│",
+            "│ㅁ │ GAME OVER! │ ㅁ│",
+            "│ㅁ └────────────┘ ㅁ│",
+            "│ㅁㅁㅁㅁㅁㅁㅁㅁㅁㅁ│",
+            "│ *ㅁㅁㅁㅁㅁㅁㅁㅁㅁ│",
+            "│ㅁㅁㅁㅁㅁㅁㅁㅁ *ㅁ│",
+            "└────────────────────┘",
+        ],
+    );
+}
+
+#[test]
+fn small_terminal_does_not_panic() {
+    for (w, h) in [(1, 1), (5, 3), (16, 5)] {
+        common::render(&mut App::new(), w, h);
+    }
+
+    let mut app = seeded_game(1, (50, 40), 500);
+    app.curr_pos = find_bomb(&app);
+    press(&mut app, "c");
+
+    for (w, h) in [(1, 1), (5, 3), (16, 5), (40, 12)] {
+        common::render(&mut app, w, h);
+    }
+}
+
+#[test]
+fn hex_board_offsets_odd_rows() {
+    let mut app = App::new();
+    app.shape = Shape::Hex;
+    app.seed = Some(2);
+    app.init_mine_map((6, 4), 3);
+    // uncover everything but the last row, so the game keeps going
+    for y in 0..3 {
+        for x in 0..6 {
+            if !app.game.board().tile(x, y).is_bomb() {
+                app.game.reveal(x, y);
+            }
+        }
+    }
+    app.curr_pos = (0, 0);
+
+    assert_screen(
+        &mut app,
+        20,
+        6,
+        &[
+            "┌Game─────────┐",
+            "│ . . . . . . │",
+            "│  1 1 . . 1 1│",
+            "│ 1ㅁ 2 . 1ㅁ │",
+            "│ ㅁㅁ 1 . 1ㅁ│",
+            "└─────────────┘",
+        ],
+    );
+}
+
+#[test]
+fn torus_board_has_double_border() {
+    let mut app = App::new();
+    app.wrap = true;
+    app.seed = Some(2);
+    app.init_mine_map((6, 3), 3);
+
+    assert_screen(
+        &mut app,
+        20,
+        5,
+        &[
+            "╔Torus═══════╗",
+            "║ㅁㅁㅁㅁㅁㅁ║",
+            "║ㅁㅁㅁㅁㅁㅁ║",
+            "║ㅁㅁㅁㅁㅁㅁ║",
+            "╚════════════╝",
+        ],
+    );
+    assert_eq!(app.game.board().neighbours(0, 0).count(), 8);
+}
+
+#[test]
+fn stacked_mines_show_counts() {
+    let mut app = App::new();
+    app.menu = false;
+    app.game = GameState::new(Board::with_mines(
+        Grid::square(6, 3),
+        3,
+        [((0, 0), 3), ((3, 2), 2), ((5, 0), 1)],
+    ));
+    app.curr_pos = (0, 0);
+    press(&mut app, "ff");
+    press(&mut app, "jlcllc");
+
+    assert_screen(
+        &mut app,
+        20,
+        5,
+        &[
+            "┌Game────────┐",
+            "│⚑2ㅁㅁㅁㅁㅁ│",
+            "│ㅁ 3ㅁ 2ㅁㅁ│",
+            "│ㅁㅁㅁㅁㅁㅁ│",
+            "└────────────┘",
+        ],
+    );
+
+    // the loss overlay covers this small board, so look at the symbols directly
+    press(&mut app, "jc");
+    let symbol = |x, y| {
+        app.theme
+            .tile_symbol_n_style(app.game.board().tile(x, y), app.glyphs)
+            .0
+    };
+    assert_eq!(symbol(0, 0), "*3");
+    assert_eq!(symbol(3, 2), "*2");
+    assert_eq!(symbol(5, 0), " *");
+}
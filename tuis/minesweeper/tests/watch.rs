@@ -0,0 +1,174 @@
+mod common;
+
+use std::{
+    net::{SocketAddr, TcpListener},
+    thread,
+    time::{Duration, Instant},
+};
+
+use common::{find_bomb, press, render, seeded_game};
+use minesweeper::{
+    app::App,
+    cli::Cli,
+    net::{Message, Mode, Peer},
+    watch::View,
+};
+use minesweeper_engine::{Status, TileContent};
+
+/// A player on a 10x8 board publishing it, with the address to watch.
+fn player() -> (App, SocketAddr) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let mut app = seeded_game(21, (10, 8), 10);
+    app.publish(listener);
+    (app, addr)
+}
+
+fn watch(player: &mut App, addr: SocketAddr) -> App {
+    let spectator = thread::spawn(move || {
+        let mut app = App::new();
+        app.watch(Peer::connect(addr, Mode::Watch).unwrap())
+            .unwrap();
+        app
+    });
+    // the player sends the view once it picks the spectator up
+    while !spectator.is_finished() {
+        player.tick();
+        thread::sleep(Duration::from_millis(1));
+    }
+    spectator.join().unwrap()
+}
+
+/// Ticks both until `done` holds, failing after a second.
+fn sync(player: &mut App, spectator: &mut App, done: impl Fn(&App) -> bool) {
+    let start = Instant::now();
+    while !done(spectator) {
+        assert!(start.elapsed() < Duration::from_secs(1), "timed out");
+        thread::sleep(Duration::from_millis(2));
+        player.tick();
+        spectator.tick();
+    }
+}
+
+#[test]
+fn views_hide_covered_tiles() {
+    let mut app = seeded_game(21, (10, 8), 10);
+    app.curr_pos = (5, 3);
+    press(&mut app, "c");
+    let view = View::new(&app.game, app.curr_pos, 4, None);
+
+    for (tile, shown) in app.game.board().tiles().iter().zip(&view.tiles) {
+        match tile.cover {
+            Some(_) => assert_eq!(shown.content, TileContent::Empty(0)),
+            None => assert_eq!(shown, tile),
+        }
+    }
+    assert_eq!(view.board().tiles(), view.tiles);
+    assert_eq!(view.summary(), "4s");
+
+    let message = Message::View(view);
+    assert_eq!(Message::from_line(&message.to_line()).unwrap(), message);
+    assert!(Message::from_line("view 2 1 square 0 standard 1 0 0 0 playing 1 - none").is_err());
+}
+
+#[test]
+fn unplayable_views_are_rejected() {
+    let view = |w: usize, h: usize, shape: &str, layers: u16| {
+        let tiles = vec!["none"; w * h].join(" ");
+        let line = format!("view {w} {h} {shape} 0 standard {layers} 0 0 0 playing 1 - {tiles}");
+        Message::from_line(&line)
+    };
+
+    assert!(view(2, 4, "square", 2).is_ok());
+    // too many layers, a height that doesn't split into them, layered hexes
+    assert!(view(2, 10, "square", 10).is_err());
+    assert!(view(2, 3, "square", 2).is_err());
+    assert!(view(2, 4, "hex", 2).is_err());
+}
+
+#[test]
+fn spectators_follow_the_game() {
+    let (mut player, addr) = player();
+    let mut spectator = watch(&mut player, addr);
+    assert!(!spectator.menu);
+    assert_eq!(spectator.curr_pos, player.curr_pos);
+
+    player.curr_pos = (5, 3);
+    press(&mut player, "c");
+    sync(&mut player, &mut spectator, |app| {
+        !app.game.board().tile(5, 3).is_covered()
+    });
+    assert_eq!(spectator.curr_pos, (5, 3));
+    assert!(spectator
+        .game
+        .board()
+        .tiles()
+        .iter()
+        .all(|tile| !tile.is_bomb()));
+
+    assert!(render(&mut player, 30, 12)[0].starts_with("┌Game (1 watching)"));
+    let screen = render(&mut spectator, 30, 12);
+    assert!(screen[0].starts_with("┌Watching"));
+    assert!(screen[9].starts_with("└0s"));
+
+    // spectators cannot play
+    press(&mut spectator, "jfc");
+    assert_eq!(spectator.curr_pos, (5, 3));
+}
+
+#[test]
+fn spectators_see_the_result() {
+    let (mut player, addr) = player();
+    let mut spectator = watch(&mut player, addr);
+
+    player.curr_pos = find_bomb(&player);
+    press(&mut player, "c");
+    sync(&mut player, &mut spectator, |app| {
+        app.watch.as_ref().unwrap().view.status == Status::Lost
+    });
+    // the mines are shown once the game is lost
+    assert!(spectator
+        .game
+        .board()
+        .tiles()
+        .iter()
+        .any(|tile| tile.is_bomb()));
+    assert_eq!(render(&mut spectator, 30, 12)[9], "└lost────────────────┘");
+    assert!(!spectator.over());
+}
+
+#[test]
+fn player_leaving_ends_the_watch() {
+    let (mut player, addr) = player();
+    let mut spectator = watch(&mut player, addr);
+    drop(player);
+
+    let start = Instant::now();
+    while !spectator.over() {
+        assert!(start.elapsed() < Duration::from_secs(1), "timed out");
+        thread::sleep(Duration::from_millis(2));
+        spectator.tick();
+    }
+    assert!(spectator.watch.as_ref().unwrap().left);
+}
+
+#[test]
+fn watch_options() {
+    let parse = |args: &str| Cli::parse(args.split_whitespace().map(String::from));
+
+    assert_eq!(parse("--publish 7879").unwrap().publish, Some(7879));
+    assert_eq!(
+        parse("--watch 7879").unwrap().watch.as_deref(),
+        Some("127.0.0.1:7879")
+    );
+    assert_eq!(
+        parse("--watch example.org:7879").unwrap().watch.as_deref(),
+        Some("example.org:7879")
+    );
+    assert!(parse("--publish many").is_err());
+
+    // only this machine can watch unless the player says otherwise
+    assert!(!parse("--publish 7879").unwrap().public);
+    assert!(parse("--publish 7879 --public").unwrap().public);
+    assert!(parse("--public").unwrap().board().is_err());
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use common::{assert_screen, click, press, render};
+use crossterm::event::MouseButton;
+use minesweeper::{app::App, board_file, theme::Zoom};
+use minesweeper_engine::TileCover;
+
+const BOARD: &str = "\
+minesweeper-board 1
+size 3 2
+*..
+...
+";
+
+fn board_app() -> App {
+    let mut app = App::new();
+    app.load_board(board_file::from_text(BOARD).unwrap());
+    app.curr_pos = (0, 0);
+    app
+}
+
+#[test]
+fn keys_change_zoom() {
+    let mut app = board_app();
+    assert_eq!(app.zoom, Zoom::Normal);
+
+    press(&mut app, "+");
+    assert_eq!(app.zoom, Zoom::Large);
+    press(&mut app, "+");
+    assert_eq!(app.zoom, Zoom::Large);
+    press(&mut app, "--");
+    assert_eq!(app.zoom, Zoom::Compact);
+    press(&mut app, "-=");
+    assert_eq!(app.zoom, Zoom::Normal);
+}
+
+#[test]
+fn compact_zoom() {
+    let mut app = board_app();
+    press(&mut app, "-fllc");
+
+    assert_screen(
+        &mut app,
+        10,
+        6,
+        &["┌Gam┐", "│⚑1.│", "│■1.│", "└───┘", "", ""],
+    );
+}
+
+#[test]
+fn hex_boards_keep_their_row_offset_when_compact() {
+    let mut app = App::new();
+    app.load_board(
+        board_file::from_text(&BOARD.replace("size 3 2", "size 3 2\nshape hex")).unwrap(),
+    );
+    press(&mut app, "-");
+
+    assert_eq!(app.zoom, Zoom::Compact);
+    assert_eq!(app.board_zoom(), Zoom::Normal);
+    let screen = render(&mut app, 20, 6);
+    assert!(screen[1].starts_with("│ㅁㅁㅁ"));
+    assert!(screen[2].starts_with("│ ㅁㅁㅁ"));
+}
+
+#[test]
+fn large_zoom() {
+    let mut app = board_app();
+    press(&mut app, "+fllc");
+
+    assert_screen(
+        &mut app,
+        20,
+        8,
+        &[
+            "┌Game─────────┐",
+            "│┌───┬───┬───┐│",
+            "││ ⚑ │ 1 │ . ││",
+            "│├───┼───┼───┤│",
+            "││ㅁ │ 1 │ . ││",
+            "│└───┴───┴───┘│",
+            "└─────────────┘",
+            "",
+        ],
+    );
+}
+
+#[test]
+fn mouse_follows_zoom() {
+    let mut app = board_app();
+    press(&mut app, "+");
+    render(&mut app, 20, 8);
+
+    // anywhere inside the second row of the first column
+    click(&mut app, MouseButton::Right, 3, 4);
+    assert_eq!(app.curr_pos, (0, 1));
+    assert_eq!(
+        app.game.board().tile(0, 1).cover,
+        Some(TileCover::FlagMark(1))
+    );
+
+    press(&mut app, "--");
+    render(&mut app, 20, 8);
+    click(&mut app, MouseButton::Left, 3, 1);
+    assert_eq!(app.curr_pos, (2, 0));
+    assert!(!app.game.board().tile(2, 0).is_covered());
+}